@@ -0,0 +1,109 @@
+//! Compares the storage layouts of `Grid` on large generated forests (the day 08 puzzle).
+//!
+//! Run with `cargo bench --bench layouts`, optionally followed by the forest sides to try, e.g.
+//! `cargo bench --bench layouts -- 500 4000`.
+
+use std::{hint::black_box, time::Instant};
+
+use aoc2022::{
+    generator,
+    helpers::{
+        grid::{ColMajor, Direction, Grid, Layout, RowMajor, Tiled},
+        rng::Rng,
+    },
+};
+
+const RUNS: usize = 5;
+
+/// `(height, visible)` of each tree.
+type Forest<L> = Grid<(u8, bool), L>;
+
+/// marks the trees visible from outside, scanning every row and every column from both ends as day 08 does.
+fn count_visible<L: Layout>(forest: &mut Forest<L>) -> usize {
+    fn scan<'a>(line: impl Iterator<Item = &'a mut (u8, bool)>) {
+        let mut max = None;
+        for (height, visible) in line {
+            if max.is_none_or(|m| *height > m) {
+                max = Some(*height);
+                *visible = true;
+                if *height == 9 {
+                    break;
+                }
+            }
+        }
+    }
+
+    for y in 0..forest.len_y() {
+        scan(forest.iter_row_mut(y));
+        scan(forest.iter_row_mut(y).rev());
+    }
+    for x in 0..forest.len_x() {
+        scan(forest.iter_col_mut(x));
+        scan(forest.iter_col_mut(x).rev());
+    }
+    forest.iter().filter(|(_, visible)| *visible).count()
+}
+
+/// returns the best scenic score, looking from each tree towards the 4 cardinal directions.
+fn best_scenic_score<L: Layout>(forest: &Forest<L>) -> usize {
+    forest
+        .iter_indexed()
+        .map(|(x, y, &(height, _))| {
+            Direction::CARDINAL
+                .iter()
+                .map(|&direction| forest.look((x, y), direction, |&(h, _)| h >= height))
+                .product()
+        })
+        .max()
+        .unwrap()
+}
+
+/// returns the sum of the heights, column by column.
+fn column_sums<L: Layout>(forest: &Forest<L>) -> u64 {
+    (0..forest.len_x())
+        .map(|x| forest.iter_col(x).map(|&(h, _)| h as u64).sum::<u64>())
+        .sum()
+}
+
+/// runs `f` `RUNS` times over a fresh copy of `forest` and returns the fastest time in milliseconds and the result.
+fn time<L: Layout, R>(forest: &Forest<L>, mut f: impl FnMut(&mut Forest<L>) -> R) -> (f64, R) {
+    let mut best = f64::MAX;
+    let mut result = None;
+    for _ in 0..RUNS {
+        let mut forest = forest.clone();
+        let start = Instant::now();
+        let r = black_box(f(&mut forest));
+        best = best.min(start.elapsed().as_secs_f64() * 1000.0);
+        result = Some(r);
+    }
+    (best, result.unwrap())
+}
+
+fn bench<L: Layout>(name: &str, forest: &Forest<RowMajor>) -> (usize, usize, u64) {
+    let forest: Forest<L> = forest.clone().to_layout();
+    let (visible_ms, visible) = time(&forest, count_visible);
+    let (scenic_ms, scenic) = time(&forest, |f| best_scenic_score(f));
+    let (sums_ms, sums) = time(&forest, |f| column_sums(f));
+    println!(
+        "{:<10} {:>12.2} {:>12.2} {:>12.2}",
+        name, visible_ms, scenic_ms, sums_ms
+    );
+    (visible, scenic, sums)
+}
+
+fn main() {
+    let sizes: Vec<usize> = std::env::args().skip(1).filter_map(|a| a.parse().ok()).collect();
+    let sizes = if sizes.is_empty() { vec![1000, 3000] } else { sizes };
+
+    for size in sizes {
+        let input = generator::day08(&mut Rng::new(size as u64), size);
+        let forest = Grid::parse(&input, |c| c.to_digit(10).map(|h| (h as u8, false)).ok_or(c)).unwrap();
+
+        println!("\n{0}x{0} forest, best of {1} runs, in ms", size, RUNS);
+        println!("{:<10} {:>12} {:>12} {:>12}", "layout", "visible", "scenic", "col sums");
+        let expected = bench::<RowMajor>("row major", &forest);
+        assert_eq!(bench::<ColMajor>("col major", &forest), expected);
+        assert_eq!(bench::<Tiled<8>>("tiled 8", &forest), expected);
+        assert_eq!(bench::<Tiled<64>>("tiled 64", &forest), expected);
+    }
+}
@@ -1,10 +1,5 @@
 use crate::helpers::read;
 
-// Constants for helping parsing the input file
-const N_COLS: usize = 9;
-const N_ROWS: usize = 8;
-const CHARS_PER_ROW: usize = 36;
-
 pub fn run() {
     let (mut crates, moves) = parse_input();
 
@@ -34,24 +29,15 @@ fn solve(crates: &mut [Vec<u8>], moves: &[Move], can_move_multiple: bool) -> Str
 //------------------------------
 
 /// Assumes the input is well-behaved and ascii
-fn parse_input() -> ([Vec<u8>; N_COLS], Vec<Move>) {
+fn parse_input() -> (Vec<Vec<u8>>, Vec<Move>) {
     let input = read::file_to_string("day05").unwrap();
-
-    let mut crates: [Vec<u8>; N_COLS] = Default::default();
+    let (drawing, procedures) = input.split_once("\n\n").unwrap();
 
     // gets the crates starting arrangement
-    input[0..N_ROWS * CHARS_PER_ROW].lines().rev().for_each(|s| {
-        let s = s.as_bytes();
-        let positions = [1usize, 5, 9, 13, 17, 21, 25, 29, 33];
-        for (n, &p) in positions.iter().enumerate() {
-            if s[p] != b' ' {
-                crates[n].push(s[p]);
-            }
-        }
-    });
+    let crates = read::columns_from_diagram(drawing);
 
     // gets the rearrangement procedures
-    let moves: Vec<Move> = input[((N_ROWS + 1) * CHARS_PER_ROW + 1)..]
+    let moves: Vec<Move> = procedures
         .lines()
         .map(|s| {
             let mut iter = s[5..].split_whitespace();
@@ -7,7 +7,7 @@ use crate::helpers::read;
 type Filesystem = HashMap<String, Item>;
 
 pub fn run() {
-    let input = read::file_to_lines("day07").unwrap().flatten();
+    let input = read::file_to_iter::<String>("day07").unwrap();
     let filesystem = process_input(input);
 
     println!("Day 07");
@@ -23,8 +23,8 @@ pub fn run() {
 
 fn sum_at_most(filesystem: &Filesystem) -> u32 {
     filesystem
-        .iter()
-        .filter_map(|(_, v)| {
+        .values()
+        .filter_map(|v| {
             if v.item_type != ItemType::File && v.size <= 100_000 {
                 Some(v.size)
             } else {
@@ -43,8 +43,8 @@ fn find_big_enough_dir(filesystem: &Filesystem) -> u32 {
     let needed_space = UPDATE_SIZE - free_space;
 
     filesystem
-        .iter()
-        .filter_map(|(_, v)| {
+        .values()
+        .filter_map(|v| {
             if v.item_type == ItemType::Dir && v.size >= needed_space {
                 Some(v.size)
             } else {
@@ -188,4 +188,19 @@ $ ls
         let filesystem = process_input(INPUT.lines().map(|l| l.to_string()));
         assert_eq!(sum_at_most(&filesystem), 95437);
     }
+
+    #[test]
+    fn teste_generated_input() {
+        for seed in 0..20 {
+            let input = crate::generator::generate(7, 300, seed).unwrap();
+            let filesystem = process_input(input.lines().map(|l| l.to_string()));
+            sum_at_most(&filesystem);
+
+            // the directory to delete frees enough space and is at most the whole disk usage
+            let used_space = filesystem["/"].size;
+            let needed_space = 30_000_000 - (70_000_000 - used_space);
+            let deleted = find_big_enough_dir(&filesystem);
+            assert!(needed_space <= deleted && deleted <= used_space);
+        }
+    }
 }
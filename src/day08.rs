@@ -1,19 +1,21 @@
 // Not the prettiest solution, but works!
 //
-// For part 2, the function `find_best_scenic_score` uses 4 loops, to process the grid once for each direction. Loops
-// usually start at the second row or column because the first one related to that given direction has values for view
-// distance as zero.
+// For part 2, the function `find_best_scenic_tree` walks rays from each tree towards the 4 cardinal directions, until
+// the view is blocked by a tree at least as tall or the edge of the grid is reached.
 
-use crate::helpers::{grid::Grid, read};
+use crate::helpers::{
+    grid::{self, Grid},
+    read,
+};
 
 #[derive(Copy, Clone)]
 struct Tree {
     height: u8,
     is_visible_from_outside: bool,
-    north_view: u8,
-    south_view: u8,
-    east_view: u8,
-    west_view: u8,
+    north_view: u32,
+    south_view: u32,
+    east_view: u32,
+    west_view: u32,
 }
 
 use Direction::*;
@@ -30,18 +32,12 @@ pub fn run() {
 
     println!("Day 08");
     println!("Part 01, Visible trees {}", count_visible_from_outside(&mut grid));
-    println!("Part 02, Best scenic score {}", find_best_scenic_score(&mut grid));
+    let (x, y, score) = find_best_scenic_tree(&mut grid);
+    println!("Part 02, Best scenic score {} at ({}, {})", score, x, y);
 }
 
 fn parse_input(input: &str) -> Grid<Tree> {
-    let len_x = input.find('\n').unwrap();
-    let flat: Vec<Tree> = input
-        .lines()
-        .flat_map(str::chars)
-        .map(|c| Tree::new(c.to_digit(10).unwrap() as u8))
-        .collect();
-
-    let mut grid = Grid::from_vec(len_x, flat.len() / len_x, flat);
+    let mut grid = Grid::parse(input, |c| c.to_digit(10).map(|h| Tree::new(h as u8)).ok_or(c)).unwrap();
 
     // set visibility of trees on the edge of the grid
     // North Edge
@@ -49,13 +45,13 @@ fn parse_input(input: &str) -> Grid<Tree> {
         .iter_mut()
         .for_each(|t| t.is_visible_from_outside = true);
     // South Edge
-    grid.row_mut(grid.len_y - 1)
+    grid.row_mut(grid.len_y() - 1)
         .iter_mut()
         .for_each(|t| t.is_visible_from_outside = true);
     // West Edge
     grid.iter_col_mut(0).for_each(|t| t.is_visible_from_outside = true);
     // East Edge
-    grid.iter_col_mut(grid.len_x - 1)
+    grid.iter_col_mut(grid.len_x() - 1)
         .for_each(|t| t.is_visible_from_outside = true);
 
     grid
@@ -69,10 +65,10 @@ fn parse_input(input: &str) -> Grid<Tree> {
 /// stop iteration if the tallest tree is found
 fn count_visible_from_outside(grid: &mut Grid<Tree>) -> usize {
     let iterate = [
-        (North, grid.len_y),
-        (South, grid.len_y),
-        (West, grid.len_x),
-        (East, grid.len_x),
+        (North, grid.len_y()),
+        (South, grid.len_y()),
+        (West, grid.len_x()),
+        (East, grid.len_x()),
     ];
 
     const TALLEST: u8 = 9;
@@ -102,99 +98,37 @@ fn count_visible_from_outside(grid: &mut Grid<Tree>) -> usize {
     grid.iter().filter(|tree| tree.is_visible_from_outside).count()
 }
 
-fn find_best_scenic_score(forest: &mut Grid<Tree>) -> u64 {
-    // scan from north, looking to the northern border
-    // for each element of a line starting from the second line from the northern border, find the view_distance looking
-    // north
-    for row in 1..forest.len_y {
-        for x in 0..forest.len_x {
-            let view_distance;
-            {
-                let curr_tree = forest.get(x, row);
-
-                view_distance = forest
-                    .iter_col(x)
-                    .take(row)
-                    .rev()
-                    .enumerate()
-                    .find(|(_, tree)| curr_tree.height <= tree.height)
-                    .map(|(i, _)| i + 1)
-                    .unwrap_or(row) as u8;
-            }
-            forest.get_mut(x, row).north_view = view_distance;
-        }
-    }
-
-    // scan from south
-    for row in (0..forest.len_y - 1).rev() {
-        for x in 0..forest.len_x {
-            let view_distance;
-            {
-                let curr_tree = forest.get(x, row);
-
-                view_distance = forest
-                    .iter_col(x)
-                    .skip(row + 1)
-                    .enumerate()
-                    .find(|(_, tree)| curr_tree.height <= tree.height)
-                    .map(|(i, _)| i + 1)
-                    .unwrap_or(forest.len_y - row - 1) as u8;
-            }
-            forest.get_mut(x, row).south_view = view_distance;
+/// returns the coordinates and the score of the tree with the best scenic score
+fn find_best_scenic_tree(forest: &mut Grid<Tree>) -> (usize, usize, u64) {
+    // for each tree, look towards each direction until a tree at least as tall blocks the view or the edge is reached
+    for y in 0..forest.len_y() {
+        for x in 0..forest.len_x() {
+            let height = forest.get(x, y).height;
+            let view_distance = |direction| forest.look((x, y), direction, |tree| tree.height >= height) as u32;
+            let north_view = view_distance(grid::Direction::North);
+            let south_view = view_distance(grid::Direction::South);
+            let west_view = view_distance(grid::Direction::West);
+            let east_view = view_distance(grid::Direction::East);
+
+            let tree = forest.get_mut(x, y);
+            tree.north_view = north_view;
+            tree.south_view = south_view;
+            tree.west_view = west_view;
+            tree.east_view = east_view;
         }
     }
 
-    // scan from western boarder
-    // for each element of a column, starting from the second column from the western border, find the view distance
-    // looking west
-    // If iterating over the columns, the view is over the row
-    for col in 1..forest.len_x {
-        for y in 0..forest.len_y {
-            let view_distance;
-            {
-                let curr_tree = forest.get(col, y);
-
-                view_distance = forest
-                    .row(y)
-                    .iter()
-                    .take(col)
-                    .rev()
-                    .enumerate()
-                    .find(|(_, tree)| curr_tree.height <= tree.height)
-                    .map(|(i, _)| i + 1)
-                    .unwrap_or(col) as u8;
-            }
-            forest.get_mut(col, y).west_view = view_distance;
-        }
-    }
-
-    // scan from east
-    for col in (0..forest.len_x - 1).rev() {
-        for y in 0..forest.len_y {
-            let view_distance;
-            {
-                let curr_tree = forest.get(col, y);
-
-                view_distance = forest
-                    .row(y)
-                    .iter()
-                    .skip(col + 1)
-                    .enumerate()
-                    .find(|(_, tree)| curr_tree.height <= tree.height)
-                    .map(|(i, _)| i + 1)
-                    .unwrap_or(forest.len_x - col - 1) as u8;
-            }
-            forest.get_mut(col, y).east_view = view_distance;
-        }
-    }
-
-    forest.iter().map(Tree::calculate_scenic_score).max().unwrap()
+    forest
+        .iter_indexed()
+        .map(|(x, y, tree)| (x, y, tree.calculate_scenic_score()))
+        .max_by_key(|&(_, _, score)| score)
+        .unwrap()
 }
 
 #[allow(dead_code)]
 fn count_visible_from_outside_old(grid: &mut Grid<Tree>) -> usize {
     // iterates over rows
-    for y in 0..grid.len_y {
+    for y in 0..grid.len_y() {
         let mut max = 0;
 
         // on normal direction
@@ -227,7 +161,7 @@ fn count_visible_from_outside_old(grid: &mut Grid<Tree>) -> usize {
     }
 
     // iterates over columns
-    for x in 0..grid.len_x {
+    for x in 0..grid.len_x() {
         let mut max = 0;
 
         // iterates on normal direction
@@ -310,8 +244,9 @@ mod tests {
     #[test]
     fn teste_find_best_scenic_score() {
         let mut grid: Grid<Tree> = GRID.clone();
-        let score = find_best_scenic_score(&mut grid);
+        let (x, y, score) = find_best_scenic_tree(&mut grid);
         assert_eq!(score, 8);
+        assert_eq!((x, y), (2, 3));
     }
 
     #[test]
@@ -323,7 +258,62 @@ mod tests {
 31234
 33333";
         let mut grid: Grid<Tree> = parse_input(input);
-        let score = find_best_scenic_score(&mut grid);
+        let (x, y, score) = find_best_scenic_tree(&mut grid);
         assert_eq!(score, 9);
+        assert_eq!((x, y), (3, 3));
+    }
+
+    #[test]
+    fn teste_long_view() {
+        // a 9 in the middle of a row of 601 trees sees 300 trees towards each side, more than fits in a u8
+        let mut middle = "1".repeat(601);
+        middle.replace_range(300..301, "9");
+        let input = format!("{0}\n{1}\n{0}", "0".repeat(601), middle);
+        let mut grid: Grid<Tree> = parse_input(&input);
+        assert_eq!(find_best_scenic_tree(&mut grid), (300, 1, 300 * 300));
+    }
+
+    #[test]
+    fn teste_generated_input() {
+        for seed in 0..5 {
+            let input = crate::generator::generate(8, 40, seed).unwrap();
+            let mut grid: Grid<Tree> = parse_input(&input);
+            let visible = count_visible_from_outside(&mut grid);
+            let old = count_visible_from_outside_old(&mut parse_input(&input));
+            assert_eq!(visible, old);
+
+            let best = brute_force_best_score(&grid);
+            let (x, y, score) = find_best_scenic_tree(&mut grid);
+            assert_eq!(score, best);
+            assert_eq!(grid.get(x, y).calculate_scenic_score(), best);
+        }
+    }
+
+    /// reference scenic score, walking from each tree towards each direction with plain loops.
+    fn brute_force_best_score(grid: &Grid<Tree>) -> u64 {
+        fn view_distance(height: u8, trees: impl Iterator<Item = u8>) -> u64 {
+            let mut distance = 0;
+            for tree in trees {
+                distance += 1;
+                if tree >= height {
+                    break;
+                }
+            }
+            distance
+        }
+
+        let height = |x: usize, y: usize| grid.get(x, y).height;
+        let mut best = 0;
+        for y in 0..grid.len_y() {
+            for x in 0..grid.len_x() {
+                let h = height(x, y);
+                let north = view_distance(h, (0..y).rev().map(|ty| height(x, ty)));
+                let south = view_distance(h, (y + 1..grid.len_y()).map(|ty| height(x, ty)));
+                let west = view_distance(h, (0..x).rev().map(|tx| height(tx, y)));
+                let east = view_distance(h, (x + 1..grid.len_x()).map(|tx| height(tx, y)));
+                best = best.max(north * south * west * east);
+            }
+        }
+        best
     }
 }
@@ -1,5 +1,5 @@
-use crate::helpers::{base2d::Base2d, read};
-use std::{cmp::Ordering, str::FromStr};
+use crate::helpers::{base2d::Base2d, read, sparse_grid::SparseGrid};
+use std::str::FromStr;
 
 type Point = Base2d<i64>;
 
@@ -20,8 +20,8 @@ pub fn run() {
 fn simulate_rope(input: &[Displacement], rope_size: usize) -> usize {
     let origin = Point::from_tuple((0, 0));
     let mut rope: Vec<Point> = vec![origin; rope_size];
-    let mut tail_positions: Vec<Option<Point>> = Vec::new();
-    tail_positions.push(Some(origin));
+    let mut tail_positions: SparseGrid<()> = SparseGrid::new();
+    tail_positions.insert(origin, ());
 
     // Simulation
     for motion in input {
@@ -61,39 +61,12 @@ fn simulate_rope(input: &[Displacement], rope_size: usize) -> usize {
                 curr_head = *tail;
             }
 
-            tail_positions.push(Some(curr_head)); // when the loop finishes, `curr_head` holds the tail
+            tail_positions.insert(curr_head, ()); // when the loop finishes, `curr_head` holds the tail
         }
     }
 
-    // Sort the `Vec` of tail positions built by the simulation
-    tail_positions.sort_unstable_by(|a, b| {
-        if let (Some(a), Some(b)) = (a, b) {
-            if a.x < b.x || a.x == b.x && a.y < b.y {
-                Ordering::Less
-            } else if a.x == b.x && a.y == b.y {
-                Ordering::Equal
-            } else {
-                Ordering::Greater
-            }
-        } else {
-            // should not be a `None` inside the `Vec` right now
-            panic!()
-        }
-    });
-
-    // Maps non unique positions to None
-    let mut iter = tail_positions.iter_mut();
-    let mut previous = iter.next().unwrap();
-    iter.for_each(|element| {
-        if element == previous {
-            *element = None;
-        } else {
-            previous = element;
-        }
-    });
-
-    // Filters and returs the total of unique positions
-    tail_positions.iter().filter_map(|&e| e).count()
+    // Each position is stored only once in the grid
+    tail_positions.len()
 }
 
 use Direction::*;
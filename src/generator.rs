@@ -0,0 +1,282 @@
+//! Random but valid puzzle inputs for stress testing the solutions.
+//!
+//! Every generator takes a `size`, whose meaning depends on the day (number of elves, lines, moves, grid side...), and
+//! a `seed`, so a failing input can always be reproduced. From the command line: `cargo run gen 8 --size 300 --seed 7`
+//! prints an input for day 08 to stdout.
+
+use std::fmt::Write;
+
+use crate::helpers::rng::Rng;
+
+/// returns an input for the puzzle of `day`, or `None` if there is no generator for it.
+pub fn generate(day: u8, size: usize, seed: u64) -> Option<String> {
+    let mut rng = Rng::new(seed);
+    let rng = &mut rng;
+    let input = match day {
+        1 => day01(rng, size),
+        2 => day02(rng, size),
+        3 => day03(rng, size),
+        4 => day04(rng, size),
+        5 => day05(rng, size),
+        6 => day06(rng, size),
+        7 => day07(rng, size),
+        8 => day08(rng, size),
+        9 => day09(rng, size),
+        _ => return None,
+    };
+    Some(input)
+}
+
+//------------------------------
+// Generators
+//------------------------------
+
+/// `size` elves, each carrying from 1 to 10 items. There are at least 3 elves, the puzzle sums the top three.
+pub fn day01(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3);
+    let elves: Vec<String> = (0..size)
+        .map(|_| {
+            let items: Vec<String> = (0..rng.range(1, 10))
+                .map(|_| rng.range(1000, 60_000).to_string())
+                .collect();
+            items.join("\n")
+        })
+        .collect();
+    elves.join("\n\n")
+}
+
+/// `size` rounds of rock, paper, scissors.
+pub fn day02(rng: &mut Rng, size: usize) -> String {
+    let rounds: Vec<String> = (0..size)
+        .map(|_| format!("{} {}", rng.choose(&['A', 'B', 'C']), rng.choose(&['X', 'Y', 'Z'])))
+        .collect();
+    rounds.join("\n")
+}
+
+/// `size` groups of three rucksacks. The halves of each rucksack share exactly one item type and the rucksacks of a
+/// group share exactly one item type, the badge.
+pub fn day03(rng: &mut Rng, size: usize) -> String {
+    let mut items: Vec<u8> = (b'a'..=b'z').chain(b'A'..=b'Z').collect();
+    let mut rucksacks = Vec::with_capacity(size * 3);
+
+    for _ in 0..size {
+        rng.shuffle(&mut items);
+        let badge = items[0];
+
+        // each rucksack of the group draws from its own pool, so the badge is the only common item
+        for pool in items[1..].chunks(17) {
+            let shared = if rng.chance(1, 10) { badge } else { pool[0] };
+            let (left_pool, right_pool) = pool[1..].split_at(8);
+            let half_len = rng.range(2, 12);
+
+            let mut left = vec![shared, badge];
+            let mut right = vec![shared];
+            left.extend((2..half_len).map(|_| *rng.choose(left_pool)));
+            right.extend((1..half_len).map(|_| *rng.choose(right_pool)));
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+
+            left.extend(right);
+            rucksacks.push(String::from_utf8(left).unwrap());
+        }
+    }
+    rucksacks.join("\n")
+}
+
+/// `size` pairs of section assignments.
+pub fn day04(rng: &mut Rng, size: usize) -> String {
+    let assignment = |rng: &mut Rng| {
+        let start = rng.range(1, 99);
+        (start, rng.range(start, 99))
+    };
+    let pairs: Vec<String> = (0..size)
+        .map(|_| {
+            let (a, b) = assignment(rng);
+            let (c, d) = assignment(rng);
+            format!("{}-{},{}-{}", a, b, c, d)
+        })
+        .collect();
+    pairs.join("\n")
+}
+
+/// nine stacks with `size` crates in total, followed by `size` valid rearrangement procedures.
+pub fn day05(rng: &mut Rng, size: usize) -> String {
+    const N_STACKS: usize = 9;
+    let mut stacks: Vec<usize> = vec![0; N_STACKS];
+    for _ in 0..size.max(1) {
+        stacks[rng.range(0, N_STACKS - 1)] += 1;
+    }
+
+    let mut input = String::new();
+    let height = *stacks.iter().max().unwrap();
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|&h| {
+                if h > level {
+                    format!("[{}]", (b'A' + rng.range(0, 25) as u8) as char)
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect();
+        writeln!(input, "{}", row.join(" ")).unwrap();
+    }
+    let labels: Vec<String> = (1..=N_STACKS).map(|n| format!(" {} ", n)).collect();
+    writeln!(input, "{}\n", labels.join(" ")).unwrap();
+
+    let mut moves = Vec::with_capacity(size);
+    for _ in 0..size {
+        let non_empty: Vec<usize> = (0..N_STACKS).filter(|&s| stacks[s] > 0).collect();
+        let from = *rng.choose(&non_empty);
+        let to = (from + rng.range(1, N_STACKS - 1)) % N_STACKS;
+        let qtd = rng.range(1, stacks[from].min(10));
+        stacks[from] -= qtd;
+        stacks[to] += qtd;
+        moves.push(format!("move {} from {} to {}", qtd, from + 1, to + 1));
+    }
+    input.push_str(&moves.join("\n"));
+    input
+}
+
+/// a datastream of at least `size` characters with a start-of-message marker somewhere.
+pub fn day06(rng: &mut Rng, size: usize) -> String {
+    const MARKER_LEN: usize = 14;
+    let size = size.max(MARKER_LEN);
+    let mut stream: Vec<u8> = (0..size).map(|_| b'a' + rng.range(0, 25) as u8).collect();
+
+    let mut marker: Vec<u8> = (b'a'..=b'z').collect();
+    rng.shuffle(&mut marker);
+    let start = rng.range(0, size - MARKER_LEN);
+    stream[start..start + MARKER_LEN].copy_from_slice(&marker[..MARKER_LEN]);
+
+    String::from_utf8(stream).unwrap()
+}
+
+/// a terminal session browsing a filesystem with `size` directories (at least one) besides the root. The total used
+/// space lands between 45M and 69M, so there is always a directory big enough to be deleted. Now and then `cd ..` is
+/// issued at the root.
+pub fn day07(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    // node 0 is the root and node 1 always lives in it, holding a big file
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); size + 1];
+    for node in 1..=size {
+        let parent = if node == 1 { 0 } else { rng.range(0, node - 1) };
+        children[parent].push(node);
+    }
+
+    // names are unique among siblings and have a fixed length, so the compound names used by the solution are unique
+    let mut names: Vec<String> = vec![String::new(); size + 1];
+    for siblings in &children {
+        for (i, &node) in siblings.iter().enumerate() {
+            let letter = (b'a' + rng.range(0, 25) as u8) as char;
+            names[node] = format!("{}{:06}", letter, i);
+        }
+    }
+
+    let files: Vec<usize> = (0..=size).map(|_| rng.range(0, 4)).collect();
+    let n_files: usize = files.iter().sum();
+    let max_file = (30_000_000 / n_files.max(1)).max(1);
+    let mut sizes: Vec<Vec<usize>> = files
+        .iter()
+        .map(|&n| (0..n).map(|_| rng.range(1, max_file)).collect())
+        .collect();
+    let used: usize = sizes.iter().flatten().sum();
+    sizes[1].push(rng.range(45_000_000, 69_000_000) - used);
+
+    let mut lines = vec!["$ cd /".to_string()];
+    if rng.chance(1, 2) {
+        lines.push("$ cd ..".to_string());
+    }
+
+    // depth first, `None` marks going back to the parent
+    let mut stack = vec![Some(0)];
+    while let Some(next) = stack.pop() {
+        let node = match next {
+            Some(node) => node,
+            None => {
+                lines.push("$ cd ..".to_string());
+                continue;
+            }
+        };
+        if node != 0 {
+            lines.push(format!("$ cd {}", names[node]));
+        }
+        lines.push("$ ls".to_string());
+        lines.extend(children[node].iter().map(|&c| format!("dir {}", names[c])));
+        lines.extend(sizes[node].iter().enumerate().map(|(i, s)| format!("{} f{}.txt", s, i)));
+
+        if node != 0 {
+            stack.push(None);
+        }
+        stack.extend(children[node].iter().rev().map(|&c| Some(c)));
+    }
+    lines.join("\n")
+}
+
+/// a `size` x `size` forest. Each row has a single tree of height 9, so large forests have trees seeing over more than
+/// 255 trees.
+pub fn day08(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let rows: Vec<String> = (0..size)
+        .map(|_| {
+            let mut row: Vec<u8> = (0..size).map(|_| b'0' + rng.range(0, 8) as u8).collect();
+            row[rng.range(0, size - 1)] = b'9';
+            String::from_utf8(row).unwrap()
+        })
+        .collect();
+    rows.join("\n")
+}
+
+/// `size` head motions.
+pub fn day09(rng: &mut Rng, size: usize) -> String {
+    let motions: Vec<String> = (0..size)
+        .map(|_| format!("{} {}", rng.choose(&['U', 'D', 'L', 'R']), rng.range(1, 20)))
+        .collect();
+    motions.join("\n")
+}
+
+//------------------------------
+// Tests
+//------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::read;
+    use std::collections::HashSet;
+
+    #[test]
+    fn teste_reproducible() {
+        for day in 1..=9 {
+            assert_eq!(generate(day, 50, 42), generate(day, 50, 42));
+        }
+        assert_ne!(generate(8, 50, 1), generate(8, 50, 2));
+        assert_eq!(generate(10, 50, 1), None);
+    }
+
+    #[test]
+    fn teste_day03_single_common_items() {
+        let input = generate(3, 200, 3).unwrap();
+        let set = |s: &str| -> HashSet<u8> { s.bytes().collect() };
+        let lines: Vec<&str> = input.lines().collect();
+        for group in lines.chunks(3) {
+            for l in group {
+                let (a, b) = l.split_at(l.len() / 2);
+                assert_eq!(set(a).intersection(&set(b)).count(), 1);
+            }
+            let common: HashSet<u8> = set(group[0]).intersection(&set(group[1])).copied().collect();
+            assert_eq!(common.intersection(&set(group[2])).count(), 1);
+        }
+    }
+
+    #[test]
+    fn teste_day05_diagram() {
+        let input = generate(5, 100, 5).unwrap();
+        let (drawing, procedures) = input.split_once("\n\n").unwrap();
+        let crates = read::columns_from_diagram(drawing);
+        assert_eq!(crates.len(), 9);
+        assert_eq!(crates.iter().map(Vec::len).sum::<usize>(), 100);
+        assert_eq!(procedures.lines().count(), 100);
+    }
+}
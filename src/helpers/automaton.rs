@@ -0,0 +1,234 @@
+//! A cellular automaton engine over a `Grid`.
+//!
+//! Each generation, a rule computes the new value of every cell from its current value and the values of its 8
+//! surrounding cells. The new values are written into a second grid, reused between generations, and then both grids
+//! are swapped. The work may be split across rows among several threads.
+//!
+//! ```
+//! use aoc2022::helpers::{
+//!     automaton::{Automaton, Border, Neighborhood},
+//!     grid::Grid,
+//! };
+//! // Conway's game of life, a blinker oscillates between a row and a column
+//! let grid = Grid::parse(".....\n..#..\n..#..\n..#..\n.....", |c| Ok::<_, ()>(c == '#')).unwrap();
+//! let mut life = Automaton::new(grid.clone(), Border::Fixed(false));
+//! let rule = |&alive: &bool, n: &Neighborhood<bool>| {
+//!     let count = n.count(|&v| v);
+//!     count == 3 || alive && count == 2
+//! };
+//! assert!(life.step(rule));
+//! assert_eq!(life.grid().row(2), &[false, true, true, true, false]);
+//! life.step(rule);
+//! assert_eq!(life.grid(), &grid);
+//! ```
+
+use std::thread;
+
+use super::grid::Grid;
+
+/// What lies beyond the edges of the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Border<T> {
+    /// every cell out of the grid has this value.
+    Fixed(T),
+    /// the grid wraps around, the cell to the left of the first column is in the last column, and so on.
+    Wrap,
+}
+
+/// The 8 cells surrounding a cell, in row major order: the 3 above, left, right and the 3 below.
+#[derive(Debug)]
+pub struct Neighborhood<'a, T> {
+    pub cells: [&'a T; 8],
+}
+
+impl<'a, T> Neighborhood<'a, T> {
+    /// the cells above, to the left, to the right and below.
+    pub fn orthogonal(&self) -> [&'a T; 4] {
+        [self.cells[1], self.cells[3], self.cells[4], self.cells[6]]
+    }
+
+    /// number of surrounding cells matching `predicate`.
+    pub fn count<P>(&self, mut predicate: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells.iter().filter(|&&c| predicate(c)).count()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<T> {
+    current: Grid<T>,
+    next: Grid<T>,
+    border: Border<T>,
+    generation: usize,
+}
+
+impl<T: Clone + PartialEq> Automaton<T> {
+    /// creates a new automaton starting from `grid`.
+    pub fn new(grid: Grid<T>, border: Border<T>) -> Automaton<T> {
+        Automaton {
+            next: grid.clone(),
+            current: grid,
+            border,
+            generation: 0,
+        }
+    }
+
+    /// the current generation.
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.current
+    }
+
+    /// number of steps taken so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// computes the next generation applying `rule` to every cell. Returns `false` if no cell changed.
+    pub fn step<F>(&mut self, rule: F) -> bool
+    where
+        F: Fn(&T, &Neighborhood<T>) -> T,
+    {
+        let (current, border) = (&self.current, &self.border);
+        let changed = self.next.rows_mut().enumerate().fold(false, |changed, (y, row)| {
+            fill_row(current, border, &rule, y, row) || changed
+        });
+        self.swap();
+        changed
+    }
+
+    /// same as `step`, but the rows are split among `threads` scoped threads.
+    pub fn step_parallel<F>(&mut self, rule: F, threads: usize) -> bool
+    where
+        F: Fn(&T, &Neighborhood<T>) -> T + Sync,
+        T: Send + Sync,
+    {
+        let rows_per_thread = self.current.len_y().div_ceil(threads.max(1)).max(1);
+        let (current, border, rule) = (&self.current, &self.border, &rule);
+
+        let changed = thread::scope(|s| {
+            let mut rows = self.next.rows_mut().enumerate().peekable();
+            let mut handles = Vec::new();
+            while rows.peek().is_some() {
+                let chunk: Vec<(usize, &mut [T])> = rows.by_ref().take(rows_per_thread).collect();
+                handles.push(s.spawn(move || {
+                    chunk.into_iter().fold(false, |changed, (y, row)| {
+                        fill_row(current, border, rule, y, row) || changed
+                    })
+                }));
+            }
+            // every thread must be joined, so no short circuit
+            let mut changed = false;
+            for handle in handles {
+                changed |= handle.join().unwrap();
+            }
+            changed
+        });
+        self.swap();
+        changed
+    }
+
+    /// steps until a generation where nothing changes or until `max_steps` steps were taken. Returns the number of
+    /// steps taken until the grid became stable, that last step included, or `None` if it did not.
+    pub fn run_until_stable<F>(&mut self, rule: F, max_steps: usize) -> Option<usize>
+    where
+        F: Fn(&T, &Neighborhood<T>) -> T,
+    {
+        (1..=max_steps).find(|_| !self.step(&rule))
+    }
+
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+    }
+}
+
+/// writes into `row` the next values of the row `y` of `current`, returning `true` if any of them changed.
+fn fill_row<T, F>(current: &Grid<T>, border: &Border<T>, rule: &F, y: usize, row: &mut [T]) -> bool
+where
+    T: PartialEq,
+    F: Fn(&T, &Neighborhood<T>) -> T,
+{
+    let mut changed = false;
+    for (x, cell) in row.iter_mut().enumerate() {
+        let value = current.get(x, y);
+        let neighborhood = neighborhood(current, border, x, y);
+        *cell = rule(value, &neighborhood);
+        changed |= cell != value;
+    }
+    changed
+}
+
+fn neighborhood<'a, T>(grid: &'a Grid<T>, border: &'a Border<T>, x: usize, y: usize) -> Neighborhood<'a, T> {
+    const OFFSETS: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+    let (len_x, len_y) = (grid.len_x() as isize, grid.len_y() as isize);
+    let cells = OFFSETS.map(|(dx, dy)| {
+        let (nx, ny) = (x as isize + dx, y as isize + dy);
+        match border {
+            _ if (0..len_x).contains(&nx) && (0..len_y).contains(&ny) => grid.get(nx as usize, ny as usize),
+            Border::Fixed(v) => v,
+            Border::Wrap => grid.get(nx.rem_euclid(len_x) as usize, ny.rem_euclid(len_y) as usize),
+        }
+    });
+    Neighborhood { cells }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::rng::Rng;
+
+    fn life(&alive: &bool, n: &Neighborhood<bool>) -> bool {
+        let count = n.count(|&v| v);
+        count == 3 || alive && count == 2
+    }
+
+    fn parse(input: &str) -> Grid<bool> {
+        Grid::parse(input, |c| Ok::<_, ()>(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn teste_still_life() {
+        let block = parse("....\n.##.\n.##.\n....");
+        let mut automaton = Automaton::new(block.clone(), Border::Fixed(false));
+        assert_eq!(automaton.run_until_stable(life, 10), Some(1));
+        assert_eq!(automaton.generation(), 1);
+        assert_eq!(automaton.into_grid(), block);
+    }
+
+    #[test]
+    fn teste_wrapping_glider() {
+        let glider = parse(".#....\n..#...\n###...\n......\n......\n......");
+
+        // on a torus of side 6, a glider is back where it started after 24 generations
+        let mut automaton = Automaton::new(glider.clone(), Border::Wrap);
+        (0..24).for_each(|_| {
+            automaton.step(life);
+        });
+        assert_eq!(automaton.grid(), &glider);
+
+        // with a fixed border it becomes a block at the corner
+        let mut automaton = Automaton::new(glider, Border::Fixed(false));
+        assert!(automaton.run_until_stable(life, 50).is_some());
+        assert_eq!(automaton.grid().iter().filter(|&&v| v).count(), 4);
+    }
+
+    #[test]
+    fn teste_parallel() {
+        let mut rng = Rng::new(40);
+        let flat: Vec<bool> = (0..37 * 23).map(|_| rng.chance(1, 3)).collect();
+        let grid = Grid::from_vec(37, 23, flat);
+
+        let mut sequential = Automaton::new(grid.clone(), Border::Wrap);
+        let mut parallel = Automaton::new(grid, Border::Wrap);
+        for threads in [1, 2, 4, 7, 50] {
+            assert_eq!(sequential.step(life), parallel.step_parallel(life, threads));
+            assert_eq!(sequential.grid(), parallel.grid());
+        }
+    }
+}
@@ -7,7 +7,7 @@ use std::{
 };
 
 /// Helper struct for representing 2d values, i.e: coordinates, indexes, etc.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Base2d<U> {
     pub x: U,
     pub y: U,
@@ -110,6 +110,14 @@ where
     }
 }
 
+/// Converts a Base2d into a tuple `(x, y)`, so it can be used wherever an `Into<(U, U)>` is expected, as the grid
+/// accessors.
+impl<U> From<Base2d<U>> for (U, U) {
+    fn from(item: Base2d<U>) -> Self {
+        (item.x, item.y)
+    }
+}
+
 impl<U> FromStr for Base2d<U>
 where
     U: FromStr + Copy,
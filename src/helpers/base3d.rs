@@ -0,0 +1,135 @@
+use std::{
+    convert::{TryFrom, TryInto},
+    error::Error,
+    fmt::Debug,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// Helper struct for representing 3d values, i.e: coordinates, indexes, etc. The 3d counterpart of `Base2d`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Base3d<U> {
+    pub x: U,
+    pub y: U,
+    pub z: U,
+}
+
+impl<U: Copy> Base3d<U> {
+    /// Constructs a new Base3d
+    pub fn new(x: U, y: U, z: U) -> Base3d<U> {
+        Base3d { x, y, z }
+    }
+
+    /// Constructs a new Base3d from a tuple
+    pub fn from_tuple(t: (U, U, U)) -> Base3d<U> {
+        Base3d { x: t.0, y: t.1, z: t.2 }
+    }
+
+    /// Returns a tuple `(x, y, z)`.
+    pub fn tuple(&self) -> (U, U, U) {
+        (self.x, self.y, self.z)
+    }
+}
+
+//--------------------------------------------------------------------
+// Operations
+//--------------------------------------------------------------------
+
+impl<U> Add for Base3d<U>
+where
+    U: Add<Output = U>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<U: AddAssign> AddAssign for Base3d<U> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<U> Sub for Base3d<U>
+where
+    U: Sub<Output = U>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<U: SubAssign> SubAssign for Base3d<U> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+//--------------------------------------------------------------------
+// Conversion traits
+//--------------------------------------------------------------------
+
+/// Defines how to convert a tuple (U, U, U) to a `Base3d<T>`, `U` must implement `TryInto<T>`. See the `Base2d`
+/// counterpart.
+impl<U, T> TryFrom<(U, U, U)> for Base3d<T>
+where
+    U: TryInto<T>,
+    <U as TryInto<T>>::Error: std::error::Error + 'static,
+{
+    type Error = Box<dyn Error>;
+
+    fn try_from(item: (U, U, U)) -> Result<Self, Self::Error> {
+        Ok(Base3d {
+            x: item.0.try_into()?,
+            y: item.1.try_into()?,
+            z: item.2.try_into()?,
+        })
+    }
+}
+
+/// Converts a Base3d into a tuple `(x, y, z)`, so it can be used wherever an `Into<(U, U, U)>` is expected.
+impl<U> From<Base3d<U>> for (U, U, U) {
+    fn from(item: Base3d<U>) -> Self {
+        (item.x, item.y, item.z)
+    }
+}
+
+impl<U> FromStr for Base3d<U>
+where
+    U: FromStr + Copy,
+    <U as FromStr>::Err: std::error::Error + 'static,
+{
+    type Err = Box<dyn Error>;
+
+    /// string needs to have three values separated by commas (','). Example: "15,21,3"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut iter = s.split(',');
+        let mut next = |name: &str| -> Result<U, Self::Err> {
+            Ok(iter
+                .next()
+                .ok_or(format!("Could not find the {} coordinate.", name))?
+                .trim()
+                .parse::<U>()?)
+        };
+        let x = next("x")?;
+        let y = next("y")?;
+        let z = next("z")?;
+        Ok(Base3d::new(x, y, z))
+    }
+}
@@ -0,0 +1,282 @@
+//! A dense two-dimensional grid of booleans, storing one bit per cell.
+//!
+//! Each row starts at a new `u64` word, the unused bits at the end of a row are always zero. This keeps row-wise
+//! operations simple: the bitwise operators between grids work word by word, and counting the set cells is a sum of
+//! `count_ones` over the words.
+//!
+//! ```
+//! use aoc2022::helpers::bit_grid::BitGrid;
+//! let mut visited = BitGrid::new(100, 100);
+//! visited.set(3, 4, true);
+//! visited.toggle(99, 99);
+//! assert_eq!(visited.count_ones(), 2);
+//! assert_eq!(visited.iter_ones().collect::<Vec<_>>(), vec![(3, 4), (99, 99)]);
+//! ```
+
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+
+use super::grid::Grid;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    words_per_row: usize,
+    len_x: usize,
+    len_y: usize,
+}
+
+impl BitGrid {
+    /// creates a `len_x` by `len_y` grid with every cell unset.
+    pub fn new(len_x: usize, len_y: usize) -> BitGrid {
+        let words_per_row = len_x.div_ceil(WORD_BITS);
+        BitGrid {
+            words: vec![0; words_per_row * len_y],
+            words_per_row,
+            len_x,
+            len_y,
+        }
+    }
+
+    /// creates a grid with the same dimensions as `grid`, setting the cells whose value matches `pred`.
+    pub fn from_grid<T, P>(grid: &Grid<T>, mut pred: P) -> BitGrid
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut bits = BitGrid::new(grid.len_x(), grid.len_y());
+        for (x, y, value) in grid.iter_indexed() {
+            if pred(value) {
+                bits.set(x, y, true);
+            }
+        }
+        bits
+    }
+
+    //------------------------------
+    // Single cells
+    //------------------------------
+
+    /// returns whether the cell at `(x, y)` is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (word, mask) = self.locate(x, y);
+        self.words[word] & mask != 0
+    }
+
+    /// sets the cell at `(x, y)` to `value`, returning its previous state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, value: bool) -> bool {
+        let (word, mask) = self.locate(x, y);
+        let previous = self.words[word] & mask != 0;
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+        previous
+    }
+
+    /// flips the cell at `(x, y)`, returning its new state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of bounds.
+    pub fn toggle(&mut self, x: usize, y: usize) -> bool {
+        let (word, mask) = self.locate(x, y);
+        self.words[word] ^= mask;
+        self.words[word] & mask != 0
+    }
+
+    /// returns the word holding `(x, y)` and the mask of its bit.
+    fn locate(&self, x: usize, y: usize) -> (usize, u64) {
+        assert!(
+            x < self.len_x && y < self.len_y,
+            "position ({}, {}) is out of bounds for a {}x{} grid",
+            x,
+            y,
+            self.len_x,
+            self.len_y
+        );
+        (y * self.words_per_row + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    //------------------------------
+    // Whole grid
+    //------------------------------
+
+    /// returns the number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// returns the number of set cells in row `y`.
+    pub fn count_ones_row(&self, y: usize) -> usize {
+        self.row_words(y).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// returns whether no cell is set.
+    pub fn is_clear(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// unsets every cell.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// returns an iterator over the coordinates of the set cells, in row major order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let y = i / self.words_per_row;
+            let x0 = (i % self.words_per_row) * WORD_BITS;
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1; // clears the lowest set bit
+                Some((x0 + bit, y))
+            })
+        })
+    }
+
+    /// returns a dense grid of booleans with the same cells.
+    pub fn to_grid(&self) -> Grid<bool> {
+        let mut grid = Grid::new(self.len_x, self.len_y, false);
+        for (x, y) in self.iter_ones() {
+            *grid.get_mut(x, y) = true;
+        }
+        grid
+    }
+
+    /// returns the words of row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is out of bounds.
+    pub fn row_words(&self, y: usize) -> &[u64] {
+        assert!(
+            y < self.len_y,
+            "row {} is out of bounds for a grid with {} rows",
+            y,
+            self.len_y
+        );
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    //------------------------------
+    // Size
+    //------------------------------
+
+    pub fn len_x(&self) -> usize {
+        self.len_x
+    }
+
+    pub fn len_y(&self) -> usize {
+        self.len_y
+    }
+
+    /// combines each word of `self` with the matching word of `other`.
+    fn zip_words(&mut self, other: &BitGrid, f: impl Fn(&mut u64, u64)) {
+        assert!(
+            self.len_x == other.len_x && self.len_y == other.len_y,
+            "cannot combine a {}x{} grid with a {}x{} one",
+            self.len_x,
+            self.len_y,
+            other.len_x,
+            other.len_y
+        );
+        self.words.iter_mut().zip(&other.words).for_each(|(a, &b)| f(a, b));
+    }
+}
+
+//------------------------------
+// Bitwise operators
+//------------------------------
+
+// The operators panic if the grids have different dimensions. Padding bits are zero in both operands, so they stay
+// zero for AND, OR and XOR.
+macro_rules! impl_bit_op {
+    ($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident, $sym:tt) => {
+        impl $op_assign<&BitGrid> for BitGrid {
+            fn $fn_assign(&mut self, rhs: &BitGrid) {
+                self.zip_words(rhs, |a, b| *a $sym b);
+            }
+        }
+
+        impl $op<&BitGrid> for &BitGrid {
+            type Output = BitGrid;
+
+            fn $fn(self, rhs: &BitGrid) -> BitGrid {
+                let mut result = self.clone();
+                result.$fn_assign(rhs);
+                result
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &=);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |=);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^=);
+
+//------------------------------
+// Tests
+//------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn teste_set_get_toggle() {
+        let mut bits = BitGrid::new(70, 3);
+        assert!(bits.is_clear());
+        assert!(!bits.set(65, 1, true));
+        assert!(bits.set(65, 1, true));
+        assert!(bits.get(65, 1));
+        assert!(!bits.get(1, 1));
+        assert!(bits.toggle(0, 2));
+        assert!(!bits.toggle(0, 2));
+        bits.set(69, 2, true);
+        assert_eq!(bits.count_ones(), 2);
+        assert_eq!(bits.count_ones_row(1), 1);
+        assert_eq!(bits.row_words(2), &[0, 1 << 5]);
+        assert_eq!(bits.iter_ones().collect::<Vec<_>>(), vec![(65, 1), (69, 2)]);
+        bits.clear();
+        assert!(bits.is_clear());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn teste_out_of_bounds() {
+        BitGrid::new(64, 1).get(64, 0);
+    }
+
+    #[test]
+    fn teste_bitwise() {
+        let grid = Grid::from_vec(3, 2, vec![1, 0, 1, 0, 1, 1]);
+        let a = BitGrid::from_grid(&grid, |&v| v == 1);
+        let b = BitGrid::from_grid(&grid, |&v| v == 0);
+        assert_eq!(
+            a.to_grid(),
+            Grid::from_vec(3, 2, vec![true, false, true, false, true, true])
+        );
+
+        assert!((&a & &b).is_clear());
+        assert_eq!((&a | &b).count_ones(), 6);
+        let mut c = a.clone();
+        c ^= &a;
+        assert!(c.is_clear());
+        c |= &b;
+        assert_eq!(c, b);
+    }
+}
@@ -0,0 +1,282 @@
+//! A two-dimensional array with dimensions known at compile time, stored inline without any heap allocation.
+//!
+//! Meant for small boards, like the 7 wide chamber of a falling rocks puzzle, where the `Vec` of a `Grid` and the
+//! index arithmetic on runtime dimensions are overhead. The accessors and iterators come from the `Board` trait, which
+//! `Grid` implements as well, while the mutable iterators over rows, columns, cells and neighbours are inherent
+//! methods, as on `Grid`.
+//!
+//! ```
+//! use aoc2022::helpers::{fixed_grid::FixedGrid, grid::Board};
+//! let mut chamber: FixedGrid<bool, 7, 4> = FixedGrid::new(false);
+//! *chamber.get_mut(2, 3) = true;
+//! chamber[(3, 3)] = true;
+//! assert_eq!(chamber.row(3), &[false, false, true, true, false, false, false]);
+//! assert_eq!(chamber.neighbors4((2, 2)).filter(|(_, _, &v)| v).count(), 1);
+//! ```
+
+use std::ops::{Index, IndexMut};
+
+use super::{
+    base2d::Base2d,
+    grid::{assert_col, assert_row, Board, Grid},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct FixedGrid<T, const W: usize, const H: usize> {
+    cells: [[T; W]; H],
+}
+
+impl<T: Clone, const W: usize, const H: usize> FixedGrid<T, W, H> {
+    /// creates a new grid with all the elements having the `init`ial value
+    pub fn new(init: T) -> Self {
+        FixedGrid {
+            cells: std::array::from_fn(|_| std::array::from_fn(|_| init.clone())),
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize> FixedGrid<T, W, H> {
+    /// returns an array for a line of the grid
+    pub fn row(&self, y: usize) -> &[T; W] {
+        &self.cells[y]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T; W] {
+        &mut self.cells[y]
+    }
+
+    /// returns all the rows, from the first to the last.
+    pub fn rows(&self) -> &[[T; W]; H] {
+        &self.cells
+    }
+
+    pub fn rows_mut(&mut self) -> &mut [[T; W]; H] {
+        &mut self.cells
+    }
+
+    /// returns the rows as an array of arrays.
+    pub fn into_rows(self) -> [[T; W]; H] {
+        self.cells
+    }
+
+    //------------------------------
+    // Mutable iterators
+    //------------------------------
+
+    /// mutable version of `Board::iter_indexed`, in row major order.
+    pub fn iter_indexed_mut(&mut self) -> impl DoubleEndedIterator<Item = (usize, usize, &mut T)> + ExactSizeIterator {
+        self.cells
+            .as_flattened_mut()
+            .iter_mut()
+            .enumerate()
+            .map(|(i, v)| (i % W, i / W, v))
+    }
+
+    /// mutable version of `Board::iter_row`.
+    pub fn iter_row_mut(&mut self, y: usize) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
+        assert_row(y, H);
+        self.cells[y].iter_mut()
+    }
+
+    /// mutable version of `Board::iter_col`.
+    pub fn iter_col_mut(&mut self, x: usize) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
+        assert_col(x, W);
+        self.cells.iter_mut().map(move |row| &mut row[x])
+    }
+
+    /// mutable version of `Board::neighbors4`.
+    pub fn neighbors4_mut(&mut self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &mut T)> {
+        let coords: Vec<_> = self.neighbors4_coords(pos).collect();
+        self.cells_mut(coords)
+    }
+
+    /// mutable version of `Board::neighbors8`.
+    pub fn neighbors8_mut(&mut self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &mut T)> {
+        let coords: Vec<_> = self.neighbors8_coords(pos).collect();
+        self.cells_mut(coords)
+    }
+
+    /// returns mutable references to the cells at `coords`, which must be in row major order without repetitions, as
+    /// the neighbours are.
+    fn cells_mut(&mut self, coords: Vec<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &mut T)> {
+        let mut coords = coords.into_iter().peekable();
+        self.iter_indexed_mut()
+            .filter(move |&(x, y, _)| coords.next_if_eq(&(x, y)).is_some())
+    }
+}
+
+impl<T, const W: usize, const H: usize> Board for FixedGrid<T, W, H> {
+    type Cell = T;
+
+    fn len_x(&self) -> usize {
+        W
+    }
+
+    fn len_y(&self) -> usize {
+        H
+    }
+
+    fn get(&self, x: usize, y: usize) -> &T {
+        &self.cells[y][x]
+    }
+
+    fn get_mut(&mut self, x: usize, y: usize) -> &mut T {
+        &mut self.cells[y][x]
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.as_flattened().iter()
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.as_flattened_mut().iter_mut()
+    }
+}
+
+//------------------------------
+// Conversions
+//------------------------------
+
+impl<T, const W: usize, const H: usize> From<[[T; W]; H]> for FixedGrid<T, W, H> {
+    fn from(cells: [[T; W]; H]) -> Self {
+        FixedGrid { cells }
+    }
+}
+
+impl<T, const W: usize, const H: usize> From<FixedGrid<T, W, H>> for Grid<T> {
+    fn from(grid: FixedGrid<T, W, H>) -> Self {
+        let flat = grid.cells.into_iter().flatten().collect();
+        Grid::from_vec(W, H, flat)
+    }
+}
+
+/// Fails if the dimensions differ, giving the grid back.
+impl<T, const W: usize, const H: usize> TryFrom<Grid<T>> for FixedGrid<T, W, H> {
+    type Error = Grid<T>;
+
+    fn try_from(grid: Grid<T>) -> Result<Self, Self::Error> {
+        if grid.len_x() != W || grid.len_y() != H {
+            return Err(grid);
+        }
+        let mut flat = grid.into_vec().into_iter();
+        Ok(FixedGrid {
+            cells: std::array::from_fn(|_| std::array::from_fn(|_| flat.next().unwrap())),
+        })
+    }
+}
+
+//------------------------------
+// Indexing
+//------------------------------
+
+impl<T, const W: usize, const H: usize> Index<(usize, usize)> for FixedGrid<T, W, H> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        &self.cells[y][x]
+    }
+}
+
+impl<T, const W: usize, const H: usize> IndexMut<(usize, usize)> for FixedGrid<T, W, H> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        &mut self.cells[y][x]
+    }
+}
+
+impl<T, const W: usize, const H: usize> Index<Base2d<usize>> for FixedGrid<T, W, H> {
+    type Output = T;
+
+    fn index(&self, pos: Base2d<usize>) -> &Self::Output {
+        &self.cells[pos.y][pos.x]
+    }
+}
+
+impl<T, const W: usize, const H: usize> IndexMut<Base2d<usize>> for FixedGrid<T, W, H> {
+    fn index_mut(&mut self, pos: Base2d<usize>) -> &mut Self::Output {
+        &mut self.cells[pos.y][pos.x]
+    }
+}
+
+//------------------------------
+// Tests
+//------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::grid::{ColMajor, Direction};
+
+    /// best scenic score of day 08, written once for any board.
+    fn best_scenic_score<B: Board<Cell = u32>>(forest: &B) -> usize {
+        forest
+            .iter_indexed()
+            .map(|(x, y, &height)| {
+                Direction::CARDINAL
+                    .iter()
+                    .map(|&direction| forest.look((x, y), direction, |&h| h >= height))
+                    .product()
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn teste_board() {
+        let grid = Grid::parse("30373\n25512\n65332\n33549\n35390", |c| c.to_digit(10).ok_or(c)).unwrap();
+        let fixed: FixedGrid<u32, 5, 5> = grid.clone().try_into().unwrap();
+        assert_eq!(best_scenic_score(&grid), 8);
+        assert_eq!(best_scenic_score(&fixed), 8);
+        assert_eq!(best_scenic_score(&grid.clone().to_layout::<ColMajor>()), 8);
+
+        assert_eq!(fixed.row(1), &[2, 5, 5, 1, 2]);
+        assert_eq!(
+            fixed.iter_col(4).collect::<Vec<_>>(),
+            grid.iter_col(4).collect::<Vec<_>>()
+        );
+        assert_eq!(fixed.position(|&h| h == 9), Some((4, 3)));
+        assert_eq!(fixed.try_get(Base2d::new(5, 0)), None);
+        assert_eq!(fixed[Base2d::new(4, 2)], 2);
+        assert_eq!(Grid::from(fixed), grid);
+    }
+
+    #[test]
+    fn teste_conversions() {
+        let mut fixed = FixedGrid::from([[1, 2, 3], [4, 5, 6]]);
+        fixed.iter_mut().for_each(|v| *v *= 10);
+        *fixed.try_get_mut(Base2d::new(0, 1)).unwrap() = 0;
+        fixed.row_mut(0)[2] = 1;
+        assert_eq!(fixed.into_rows(), [[10, 20, 1], [0, 50, 60]]);
+
+        let grid = Grid::new(3, 2, 0u8);
+        assert!(FixedGrid::<u8, 3, 2>::try_from(grid.clone()).is_ok());
+        assert_eq!(FixedGrid::<u8, 2, 3>::try_from(grid.clone()), Err(grid));
+    }
+
+    #[test]
+    fn teste_mutable_iterators() {
+        let mut fixed = FixedGrid::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        fixed.iter_row_mut(0).rev().zip(1..).for_each(|(v, i)| *v *= i);
+        fixed.iter_col_mut(2).for_each(|v| *v = 0);
+        for (_, _, v) in fixed.neighbors4_mut((0, 0)) {
+            *v += 100;
+        }
+        for (_, _, v) in fixed.neighbors8_mut((1, 1)) {
+            *v += 1;
+        }
+        assert_eq!(fixed.into_rows(), [[4, 105, 1], [105, 5, 1], [8, 9, 1]]);
+
+        let mut fixed = FixedGrid::<usize, 3, 2>::new(0);
+        fixed.iter_indexed_mut().for_each(|(x, y, v)| *v = x + 10 * y);
+        assert_eq!(fixed.into_rows(), [[0, 1, 2], [10, 11, 12]]);
+    }
+
+    #[test]
+    fn teste_board_matches_grid() {
+        // going through the trait gives the same results as the inherent methods, whatever the layout
+        let grid: Grid<u32, ColMajor> = Grid::from_vec(3, 2, vec![0, 1, 2, 1, 0, 0]).to_layout();
+        assert_eq!(grid.position(|&v| v == 1), Some((1, 0)));
+        assert_eq!(Board::position(&grid, |&v| v == 1), Some((1, 0)));
+        assert_eq!(Board::find(&grid, |&v| v == 1), grid.find(|&v| v == 1));
+        assert!(Board::iter_indexed(&grid).eq(grid.iter_indexed()));
+    }
+}
@@ -1,47 +1,125 @@
 //! A two-dimensional array using a flat internal representation.
 //!
-//! This is a row major implementation, consecutive elements across the x
-//! dimension are next to each other, whereas columns are strided.
+//! By default this is a row major implementation, consecutive elements across
+//! the x dimension are next to each other, whereas columns are strided. See
+//! Layout below for the alternatives.
 //!
 //! `x` represents variation in row elements (which column the value is in),
 //! whereas `y` represents a change in column elements (which row is it in). The
-//! grid may be indexed with a tuple using `get_from2d((x,y))` , for example:
+//! grid may be indexed with a `(x, y)` tuple or a `Base2d<usize>`, for example:
 //!
-//! - get_from2d((5, 0)) returns the sixth element of the first row. It can also be interpreted as the the element at
-//!   column 5 and row 0.
+//! - `grid[(5, 0)]` returns the sixth element of the first row. It can also be interpreted as the element at column 5
+//!   and row 0.
 //!
-//! - get_from2d((1, 5)) returns the second element of the sixth row. In other words, the element at column 1 and row 5.
+//! - `grid[Base2d::new(1, 5)]` returns the second element of the sixth row. In other words, the element at column 1
+//!   and row 5.
 //!
 //! # Indexing
 //!
-//! Implements the Index trait, so the grid may be read by a tuple inside
-//! square brackets. Example:
+//! Implements the Index and IndexMut traits, so the grid may be accessed by a
+//! tuple or a `Base2d<usize>` inside square brackets. Example:
 //!
 //! ```
-//! use adv20::helpers::grid::Grid;
+//! use aoc2022::helpers::{base2d::Base2d, grid::Grid};
 //! let mut grid = Grid::new(5, 5, 0u8);
 //! let v = grid.get_mut(2, 2);
 //! *v = 100;
 //! assert_eq!(grid[(2,2)], 100);
+//! grid[Base2d::new(4, 0)] = 7;
+//! assert_eq!(grid[(4, 0)], 7);
 //! ```
 //!
-//! ## Beware
+//! Signed coordinates, which may come from adding offsets to a position, can be
+//! checked with `try_get` and `try_get_mut`:
 //!
-//! If no inferring is made, the Default type for tuples in rust is i32.
+//! ```
+//! use aoc2022::helpers::{base2d::Base2d, grid::Grid};
+//! let grid = Grid::new(5, 5, 0u8);
+//! assert_eq!(grid.try_get(Base2d::new(4, 4)), Some(&0));
+//! assert_eq!(grid.try_get(Base2d::new(-1, 2)), None);
+//! assert_eq!(grid.try_get(Base2d::new(5, 0)), None);
+//! ```
 //!
 //! # Panics
 //!
-//! Panics if the indexing inside square brackets is done with negative values.
+//! Panics if the indexing inside square brackets is done out of bounds.
+//!
+//! # Resizing
+//!
+//! The dimensions are read with `len_x()` and `len_y()`, and only change
+//! through the operations inserting or removing rows and columns, padding or
+//! cropping the grid, so they always match the inner representation.
+//!
+//! # Topology
+//!
+//! The grid is bounded, accessing cells past the edges panics or returns
+//! `None`. Wrap it in a `Torus` for coordinates that wrap around the edges.
+//!
+//! # Layout
+//!
+//! The order of the cells in memory is chosen by the second type parameter:
+//! `RowMajor` (the default), `ColMajor` or `Tiled`. The layout only changes
+//! the speed of the accessors, iterators and algorithms, which give the same
+//! results, in row major order, with any layout. There are two exceptions:
+//!
+//! - `iter` and `iter_mut` follow the storage order, they are the fastest way
+//!   to visit every cell when the order does not matter.
+//! - parsing, row slices (`row`, `rows`, `into_vec`), views, windows,
+//!   resizing and `Automaton` need contiguous rows, so they are only available
+//!   with the default layout.
+//!
+//! A grid is moved to another layout with `to_layout`.
+//!
+//! # Traversal
+//!
+//! Besides rows and columns, the cells may be visited along the diagonals and
+//! anti-diagonals, in spiral order or in snake order.
+//!
+//! # Rendering
+//!
+//! A grid may be rendered as text with `to_text`, or with `Display` if the
+//! cells implement it, and exported as an image with `write_pgm` and
+//! `write_ppm`.
+
+use std::{
+    error::Error,
+    fmt,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+use super::base2d::Base2d;
 
-// use std::{convert::TryInto, fmt::Debug, ops::Index};
+mod board;
+mod diff;
+mod layout;
+mod pattern;
+mod regions;
+mod render;
+mod resize;
+mod torus;
+mod traversal;
+mod window;
 
-// use super::base2d::Base2d;
+pub use board::Board;
+pub use diff::CellDiff;
+pub use layout::{ColMajor, Layout, RowMajor, Tiled};
+pub use pattern::{Orientation, PatternMatch};
+pub use regions::{Component, Connectivity};
+pub use torus::Torus;
+pub use window::SummedAreaTable;
+
+use layout::{CellsMut, Either};
+
+/// A marker character found while parsing a grid and its position.
+pub type Marker = (char, Base2d<usize>);
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct Grid<T> {
+pub struct Grid<T, L: Layout = RowMajor> {
     flat: Vec<T>,
-    pub len_x: usize,
-    pub len_y: usize,
+    len_x: usize,
+    len_y: usize,
+    layout: PhantomData<L>,
 }
 
 impl<T: Clone> Grid<T> {
@@ -51,26 +129,244 @@ impl<T: Clone> Grid<T> {
             flat: vec![init; len_x * len_y],
             len_x,
             len_y,
+            layout: PhantomData,
         }
     }
+}
 
+impl<T> Grid<T> {
     /// If vector `v` is larger than `len_x` * `len_y`, the extra elements are
     /// truncated.
     ///
     /// # Panics
     ///
-    /// - The input vector `v` must have at least `len_x` * `len_y` lenght.
-    /// Otherwise the program may panic while trying to access the elements of
-    /// the inner vector;
-    pub fn from_vec(len_x: usize, len_y: usize, mut v: Vec<T>) -> Grid<T> {
-        debug_assert!(v.len() >= len_x * len_y);
-        v.truncate(len_x * len_y);
+    /// Panics if the input vector `v` has less than `len_x` * `len_y` elements.
+    /// See `try_from_vec` for a checked version.
+    pub fn from_vec(len_x: usize, len_y: usize, v: Vec<T>) -> Grid<T> {
+        match Self::try_from_vec(len_x, len_y, v) {
+            Ok(grid) => grid,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Same as `from_vec`, but returns an error if the input vector `v` has less
+    /// than `len_x` * `len_y` elements.
+    pub fn try_from_vec(len_x: usize, len_y: usize, mut v: Vec<T>) -> Result<Grid<T>, GridSizeError> {
+        let expected = len_x * len_y;
+        if v.len() < expected {
+            return Err(GridSizeError {
+                expected,
+                found: v.len(),
+            });
+        }
+        v.truncate(expected);
 
-        Grid { flat: v, len_x, len_y }
+        Ok(Grid {
+            flat: v,
+            len_x,
+            len_y,
+            layout: PhantomData,
+        })
     }
 }
 
+impl<T: Clone, L: Layout> Grid<T, L> {
+    //------------------------------
+    // Transforms
+    //------------------------------
+    //
+    // The transforms return a new grid, the original one is left untouched.
+
+    /// returns a grid where rows became columns, the value at `(x, y)` goes to `(y, x)`.
+    pub fn transpose(&self) -> Grid<T, L> {
+        self.remap(self.len_y, self.len_x, |x, y| (y, x))
+    }
+
+    /// returns the grid rotated 90 degrees clockwise, the first row becomes the last column.
+    pub fn rotate_cw(&self) -> Grid<T, L> {
+        self.remap(self.len_y, self.len_x, |x, y| (y, self.len_y - 1 - x))
+    }
+
+    /// returns the grid rotated 90 degrees counterclockwise, the first row becomes the first column (upside down).
+    pub fn rotate_ccw(&self) -> Grid<T, L> {
+        self.remap(self.len_y, self.len_x, |x, y| (self.len_x - 1 - y, x))
+    }
+
+    /// returns the grid mirrored along the x axis, each row is reversed.
+    pub fn flip_x(&self) -> Grid<T, L> {
+        self.remap(self.len_x, self.len_y, |x, y| (self.len_x - 1 - x, y))
+    }
+
+    /// returns the grid mirrored along the y axis, the order of the rows is reversed.
+    pub fn flip_y(&self) -> Grid<T, L> {
+        self.remap(self.len_x, self.len_y, |x, y| (x, self.len_y - 1 - y))
+    }
+
+    /// builds a new `len_x` by `len_y` grid, where `source` returns the coordinates in `self` for each new cell.
+    fn remap<F>(&self, len_x: usize, len_y: usize, source: F) -> Grid<T, L>
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let flat = (0..len_x * len_y)
+            .map(|i| {
+                let (x, y) = L::coords(i, len_x, len_y);
+                let (sx, sy) = source(x, y);
+                self.get(sx, sy).clone()
+            })
+            .collect();
+        Grid {
+            flat,
+            len_x,
+            len_y,
+            layout: PhantomData,
+        }
+    }
+}
+
+// Parsing, row slices and views rely on contiguous rows, so they are only available with the default layout.
 impl<T> Grid<T> {
+    //------------------------------
+    // Parsing
+    //------------------------------
+
+    /// Builds a grid from a character map, one row per line. Each character is converted into a cell by `f`.
+    ///
+    /// Trailing new lines are ignored. Returns an error if the input is empty, if a row has a different width than
+    /// the first one or if `f` fails for any character.
+    pub fn parse<F, E>(input: &str, f: F) -> Result<Grid<T>, ParseGridError<E>>
+    where
+        F: FnMut(char) -> Result<T, E>,
+    {
+        Self::parse_with_markers(input, &[], f).map(|(grid, _)| grid)
+    }
+
+    /// Same as `parse`, but also returns the positions of every character in `markers` found in the input (i.e.:
+    /// start and end points), in row major order. Marker characters are still handed to `f` to be converted into a
+    /// cell.
+    pub fn parse_with_markers<F, E>(
+        input: &str,
+        markers: &[char],
+        mut f: F,
+    ) -> Result<(Grid<T>, Vec<Marker>), ParseGridError<E>>
+    where
+        F: FnMut(char) -> Result<T, E>,
+    {
+        let input = input.trim_end_matches(['\n', '\r']);
+        if input.is_empty() {
+            return Err(ParseGridError::Empty);
+        }
+
+        let mut flat = Vec::new();
+        let mut found = Vec::new();
+        let mut len_x = 0;
+        let mut len_y = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let mut width = 0;
+            for (col, c) in line.chars().enumerate() {
+                if markers.contains(&c) {
+                    found.push((c, Base2d::new(col, row)));
+                }
+                flat.push(f(c).map_err(|error| ParseGridError::Cell { row, col, error })?);
+                width += 1;
+            }
+
+            if row == 0 {
+                len_x = width;
+            } else if width != len_x {
+                return Err(ParseGridError::Ragged {
+                    row,
+                    col: width.min(len_x),
+                    expected: len_x,
+                    found: width,
+                });
+            }
+            len_y += 1;
+        }
+
+        if len_x == 0 {
+            return Err(ParseGridError::Empty);
+        }
+
+        Ok((
+            Grid {
+                flat,
+                len_x,
+                len_y,
+                layout: PhantomData,
+            },
+            found,
+        ))
+    }
+
+    //------------------------------
+    // Rows
+    //------------------------------
+
+    // returns an array slice for a line of the grid
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(
+            y < self.len_y,
+            "row {} is out of bounds, there are {} rows",
+            y,
+            self.len_y
+        );
+        &self.flat[y * self.len_x..(y + 1) * self.len_x]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        assert!(
+            y < self.len_y,
+            "row {} is out of bounds, there are {} rows",
+            y,
+            self.len_y
+        );
+        &mut self.flat[y * self.len_x..(y + 1) * self.len_x]
+    }
+
+    /// returns the cells in row major order, consuming the grid.
+    pub fn into_vec(self) -> Vec<T> {
+        self.flat
+    }
+
+    /// returns an iterator over the rows of the grid as array slices, from the first to the last. A grid 0 wide has no
+    /// cells to slice, so it yields no rows whatever its `len_y`, use `row` to get its empty rows one by one.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.flat.chunks(self.len_x.max(1))
+    }
+
+    /// mutable version of `rows`. Each row may be handed to a different thread.
+    pub fn rows_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut [T]> + ExactSizeIterator {
+        self.flat.chunks_mut(self.len_x.max(1))
+    }
+
+    //------------------------------
+    // Views
+    //------------------------------
+
+    /// returns a borrowed view of the rectangular region with the top left corner at `(x, y)` and `len_x` by `len_y`
+    /// cells. The view is indexed from its own top left corner.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region does not fit inside the grid.
+    pub fn view(&self, x: usize, y: usize, len_x: usize, len_y: usize) -> GridView<'_, T> {
+        self.as_view().view(x, y, len_x, len_y)
+    }
+
+    /// returns a borrowed view of the whole grid.
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            x0: 0,
+            y0: 0,
+            len_x: self.len_x,
+            len_y: self.len_y,
+        }
+    }
+}
+
+impl<T, L: Layout> Grid<T, L> {
     //------------------------------
     // Getters for single elements
     //------------------------------
@@ -81,49 +377,256 @@ impl<T> Grid<T> {
     ///
     /// Panics if either index is out of bounds.
     pub fn get(&self, x: usize, y: usize) -> &T {
+        self.assert_in_bounds(x, y);
         &self.flat[self.index(x, y)]
     }
 
     pub fn get_mut(&mut self, x: usize, y: usize) -> &mut T {
+        self.assert_in_bounds(x, y);
         let i = self.index(x, y); // must have an aux variable coz mutable borrow
         &mut self.flat[i]
     }
 
+    /// returns the value at position `pos`, or `None` if it is out of bounds.
+    /// Negative coordinates are always out of bounds.
+    pub fn try_get(&self, pos: Base2d<i64>) -> Option<&T> {
+        let (x, y) = checked_coords(self.len_x, self.len_y, pos)?;
+        Some(&self.flat[self.index(x, y)])
+    }
+
+    pub fn try_get_mut(&mut self, pos: Base2d<i64>) -> Option<&mut T> {
+        let (x, y) = checked_coords(self.len_x, self.len_y, pos)?;
+        let i = self.index(x, y);
+        Some(&mut self.flat[i])
+    }
+
     //------------------------------
     // Getters for multiple elements
     //------------------------------
 
-    pub fn iter(&self) -> impl Iterator<Item = &T> {
+    /// returns an iterator over the values in storage order, which is row major for the default layout.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
         self.flat.iter()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
         self.flat.iter_mut()
     }
 
-    // returns an array slice for a line of the grid
-    pub fn row(&self, y: usize) -> &[T] {
-        &self.flat[self.index(0, y)..=self.index(self.len_x - 1, y)]
+    /// returns an iterator over every cell along with its coordinates, in row major order with any layout.
+    pub fn iter_indexed(&self) -> impl DoubleEndedIterator<Item = (usize, usize, &T)> + ExactSizeIterator {
+        let (len_x, len_y) = (self.len_x, self.len_y);
+        (0..len_x * len_y).map(move |i| {
+            let (x, y) = RowMajor::coords(i, len_x, len_y);
+            (x, y, &self.flat[L::index(x, y, len_x, len_y)])
+        })
     }
 
-    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
-        let idx0 = self.index(0, y);
-        let idx1 = self.index(self.len_x - 1, y);
-        &mut self.flat[idx0..=idx1]
+    pub fn iter_indexed_mut(&mut self) -> impl DoubleEndedIterator<Item = (usize, usize, &mut T)> + ExactSizeIterator {
+        let (len_x, len_y) = (self.len_x, self.len_y);
+        if L::ROW_MAJOR {
+            return Either::Left(self.flat.iter_mut().enumerate().map(move |(i, v)| {
+                let (x, y) = RowMajor::coords(i, len_x, len_y);
+                (x, y, v)
+            }));
+        }
+        // the cells are borrowed in storage order, then handed in row major order
+        let mut cells: Vec<Option<&mut T>> = self.flat.iter_mut().map(Some).collect();
+        Either::Right((0..len_x * len_y).map(move |i| {
+            let (x, y) = RowMajor::coords(i, len_x, len_y);
+            (x, y, cells[L::index(x, y, len_x, len_y)].take().unwrap())
+        }))
+    }
+
+    /// returns an iterator over the values of row `y`, from left to right. Works with any layout, see `row` for a
+    /// slice.
+    pub fn iter_row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        Board::iter_row(self, y)
+    }
+
+    pub fn iter_row_mut(&mut self, y: usize) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
+        assert_row(y, self.len_y);
+        let (len_x, len_y) = (self.len_x, self.len_y);
+        CellsMut::new(&mut self.flat, (0..len_x).map(move |x| L::index(x, y, len_x, len_y)))
     }
 
+    /// returns an iterator over the values of column `x`, from top to bottom.
     pub fn iter_col(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
-        self.flat.iter().skip(x).step_by(self.len_x)
+        Board::iter_col(self, x)
     }
 
     pub fn iter_col_mut(&mut self, x: usize) -> impl DoubleEndedIterator<Item = &mut T> + ExactSizeIterator {
-        self.flat.iter_mut().skip(x).step_by(self.len_x)
+        assert_col(x, self.len_x);
+        let (len_x, len_y) = (self.len_x, self.len_y);
+        CellsMut::new(&mut self.flat, (0..len_y).map(move |y| L::index(x, y, len_x, len_y)))
+    }
+
+    /// moves the cells to a grid with another storage layout.
+    pub fn to_layout<M: Layout>(self) -> Grid<T, M> {
+        let (len_x, len_y) = (self.len_x, self.len_y);
+        let mut cells: Vec<Option<T>> = self.flat.into_iter().map(Some).collect();
+        let flat = (0..cells.len())
+            .map(|i| {
+                let (x, y) = M::coords(i, len_x, len_y);
+                cells[L::index(x, y, len_x, len_y)].take().unwrap()
+            })
+            .collect();
+        Grid {
+            flat,
+            len_x,
+            len_y,
+            layout: PhantomData,
+        }
+    }
+
+    //------------------------------
+    // Mapping and searching
+    //------------------------------
+
+    /// returns a new grid with the same dimensions, where each cell is `f` applied to the matching cell of `self`. The
+    /// cells are handed to `f` in row major order.
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U, L>
+    where
+        F: FnMut(&T) -> U,
+    {
+        if L::ROW_MAJOR {
+            return Grid::from_row_major(self.len_x, self.len_y, self.flat.iter().map(f).collect());
+        }
+        let values = self.iter_indexed().map(|(_, _, v)| f(v)).collect();
+        Grid::from_row_major(self.len_x, self.len_y, values)
+    }
+
+    /// returns a new grid where each cell is `f` applied to the matching cells of `self` and `other`. The cells are
+    /// handed to `f` in row major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grids have different dimensions.
+    pub fn zip_with<U, V, F>(&self, other: &Grid<U, L>, mut f: F) -> Grid<V, L>
+    where
+        F: FnMut(&T, &U) -> V,
+    {
+        assert!(
+            self.len_x == other.len_x && self.len_y == other.len_y,
+            "cannot zip a {}x{} grid with a {}x{} one",
+            self.len_x,
+            self.len_y,
+            other.len_x,
+            other.len_y
+        );
+        let values = if L::ROW_MAJOR {
+            self.flat.iter().zip(&other.flat).map(|(a, b)| f(a, b)).collect()
+        } else {
+            self.iter_indexed()
+                .zip(other.iter_indexed())
+                .map(|((_, _, a), (_, _, b))| f(a, b))
+                .collect()
+        };
+        Grid::from_row_major(self.len_x, self.len_y, values)
+    }
+
+    /// returns the coordinates of the first cell, in row major order, whose value matches `pred`.
+    pub fn position<P>(&self, pred: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.find(pred).map(|(x, y, _)| (x, y))
+    }
+
+    /// returns the first cell, in row major order, whose value matches `pred`, along with its coordinates.
+    pub fn find<P>(&self, mut pred: P) -> Option<(usize, usize, &T)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter_indexed().find(|(_, _, v)| pred(v))
+    }
+
+    //------------------------------
+    // Neighbours
+    //------------------------------
+    //
+    // The neighbours are yielded in row major order, cells out of the grid are skipped. The position may be a tuple
+    // `(x, y)` or a `Base2d<usize>`.
+
+    /// returns an iterator over the coordinates of the cells above, to the left, to the right and below `pos`.
+    pub fn neighbors4_coords(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize)> {
+        neighbor_coords(self.len_x, self.len_y, pos.into(), &NEIGHBORS4)
+    }
+
+    /// returns an iterator over the coordinates of the 8 cells surrounding `pos`, diagonals included.
+    pub fn neighbors8_coords(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize)> {
+        neighbor_coords(self.len_x, self.len_y, pos.into(), &NEIGHBORS8)
+    }
+
+    /// returns an iterator over `(x, y, &value)` of the cells above, to the left, to the right and below `pos`.
+    pub fn neighbors4(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &T)> {
+        Board::neighbors4(self, pos)
+    }
+
+    /// returns an iterator over `(x, y, &value)` of the 8 cells surrounding `pos`, diagonals included.
+    pub fn neighbors8(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &T)> {
+        Board::neighbors8(self, pos)
+    }
+
+    /// mutable version of `neighbors4`.
+    pub fn neighbors4_mut(&mut self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &mut T)> {
+        let coords: Vec<_> = self.neighbors4_coords(pos).collect();
+        self.cells_mut(coords)
+    }
+
+    /// mutable version of `neighbors8`.
+    pub fn neighbors8_mut(&mut self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &mut T)> {
+        let coords: Vec<_> = self.neighbors8_coords(pos).collect();
+        self.cells_mut(coords)
+    }
+
+    //------------------------------
+    // Rays
+    //------------------------------
+
+    /// returns an iterator over `(x, y)` of the cells walking from `pos` towards `direction` until the edge of the
+    /// grid. The cell at `pos` itself is not included.
+    pub fn ray_coords(
+        &self,
+        pos: impl Into<(usize, usize)>,
+        direction: Direction,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        ray_coords(self.len_x, self.len_y, pos.into(), direction)
+    }
+
+    /// returns an iterator over `(x, y, &value)` of the cells walking from `pos` towards `direction` until the edge
+    /// of the grid. The cell at `pos` itself is not included.
+    pub fn ray(
+        &self,
+        pos: impl Into<(usize, usize)>,
+        direction: Direction,
+    ) -> impl Iterator<Item = (usize, usize, &T)> {
+        Board::ray(self, pos, direction)
+    }
+
+    /// walks from `pos` towards `direction` and returns how many cells are seen until the first one for which
+    /// `is_blocking` returns `true`, that cell included. If no cell blocks the view, returns the distance to the edge.
+    pub fn look<F>(&self, pos: impl Into<(usize, usize)>, direction: Direction, is_blocking: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        Board::look(self, pos, direction, is_blocking)
     }
 
     //------------------------------
     // Helpers
     //------------------------------
 
+    /// number of columns, the length of each row.
+    pub fn len_x(&self) -> usize {
+        self.len_x
+    }
+
+    /// number of rows, the length of each column.
+    pub fn len_y(&self) -> usize {
+        self.len_y
+    }
+
     /// returns the total size of the array (len_x * len_y)
     pub fn size(&self) -> usize {
         self.flat.len()
@@ -136,6 +639,692 @@ impl<T> Grid<T> {
     /// returns the index for acessing the `flat` array from the coordinates `x`
     /// and `y`.
     fn index(&self, x: usize, y: usize) -> usize {
-        self.len_x * y + x
+        L::index(x, y, self.len_x, self.len_y)
+    }
+
+    /// panics if `(x, y)` is out of bounds, the flat index of a column past the last one would land on the next row.
+    fn assert_in_bounds(&self, x: usize, y: usize) {
+        assert!(
+            x < self.len_x && y < self.len_y,
+            "position ({}, {}) is out of bounds for a {}x{} grid",
+            x,
+            y,
+            self.len_x,
+            self.len_y
+        );
+    }
+
+    /// builds a grid from its cells given in row major order.
+    fn from_row_major(len_x: usize, len_y: usize, values: Vec<T>) -> Grid<T, L> {
+        if L::ROW_MAJOR {
+            return Grid {
+                flat: values,
+                len_x,
+                len_y,
+                layout: PhantomData,
+            };
+        }
+        let mut values: Vec<Option<T>> = values.into_iter().map(Some).collect();
+        let flat = (0..values.len())
+            .map(|i| {
+                let (x, y) = L::coords(i, len_x, len_y);
+                values[RowMajor::index(x, y, len_x, len_y)].take().unwrap()
+            })
+            .collect();
+        Grid {
+            flat,
+            len_x,
+            len_y,
+            layout: PhantomData,
+        }
+    }
+
+    /// returns mutable references to the cells at `coords`, in the same order. The coordinates must be in bounds and
+    /// without repetitions.
+    fn cells_mut(&mut self, coords: Vec<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &mut T)> {
+        // the cells are borrowed in storage order, then put back in the requested order
+        let mut order: Vec<usize> = (0..coords.len()).collect();
+        order.sort_unstable_by_key(|&k| self.index(coords[k].0, coords[k].1));
+        let indices: Vec<usize> = order.iter().map(|&k| self.index(coords[k].0, coords[k].1)).collect();
+
+        let mut cells: Vec<Option<&mut T>> = (0..coords.len()).map(|_| None).collect();
+        for (k, cell) in order
+            .into_iter()
+            .zip(CellsMut::new(&mut self.flat, indices.into_iter()))
+        {
+            cells[k] = Some(cell);
+        }
+        coords
+            .into_iter()
+            .zip(cells)
+            .map(|((x, y), cell)| (x, y, cell.unwrap()))
+    }
+}
+
+/// converts signed coordinates into a position of a `len_x` by `len_y` grid, returning `None` if they are out of bounds.
+fn checked_coords(len_x: usize, len_y: usize, pos: Base2d<i64>) -> Option<(usize, usize)> {
+    let x = usize::try_from(pos.x).ok().filter(|&x| x < len_x)?;
+    let y = usize::try_from(pos.y).ok().filter(|&y| y < len_y)?;
+    Some((x, y))
+}
+
+/// panics if row `y` is out of bounds of a grid with `len_y` rows.
+pub(super) fn assert_row(y: usize, len_y: usize) {
+    assert!(y < len_y, "row {} is out of bounds for a grid with {} rows", y, len_y);
+}
+
+/// panics if column `x` is out of bounds of a grid with `len_x` columns.
+pub(super) fn assert_col(x: usize, len_x: usize) {
+    assert!(
+        x < len_x,
+        "column {} is out of bounds for a grid with {} columns",
+        x,
+        len_x
+    );
+}
+
+/// returns how many `cells` are seen until the first one for which `is_blocking` returns `true`, that one included.
+fn count_seen<'a, T: 'a, F>(cells: impl Iterator<Item = &'a T>, mut is_blocking: F) -> usize
+where
+    F: FnMut(&T) -> bool,
+{
+    let mut seen = 0;
+    for v in cells {
+        seen += 1;
+        if is_blocking(v) {
+            break;
+        }
+    }
+    seen
+}
+
+/// returns the coordinates `pos` + `offset` for each offset that lands inside a grid of `len_x` by `len_y`.
+fn neighbor_coords(
+    len_x: usize,
+    len_y: usize,
+    (x, y): (usize, usize),
+    offsets: &'static [(isize, isize)],
+) -> impl Iterator<Item = (usize, usize)> {
+    offsets.iter().filter_map(move |&(dx, dy)| {
+        let nx = x.checked_add_signed(dx).filter(|&nx| nx < len_x)?;
+        let ny = y.checked_add_signed(dy).filter(|&ny| ny < len_y)?;
+        Some((nx, ny))
+    })
+}
+
+/// returns the coordinates walking from `pos` towards `direction` until the edge of a grid of `len_x` by `len_y`.
+fn ray_coords(
+    len_x: usize,
+    len_y: usize,
+    (mut x, mut y): (usize, usize),
+    direction: Direction,
+) -> impl Iterator<Item = (usize, usize)> {
+    let (dx, dy) = direction.offset();
+    std::iter::from_fn(move || {
+        x = x.checked_add_signed(dx).filter(|&nx| nx < len_x)?;
+        y = y.checked_add_signed(dy).filter(|&ny| ny < len_y)?;
+        Some((x, y))
+    })
+}
+
+/// offsets of the orthogonal neighbours, in row major order.
+const NEIGHBORS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// offsets of the orthogonal and diagonal neighbours, in row major order.
+const NEIGHBORS8: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+//--------------------------------------------------------------------
+// Views
+//--------------------------------------------------------------------
+
+/// A zero copy view of a rectangular region of a `Grid`, created by `Grid::view`. It supports the same read API as the
+/// grid, with coordinates relative to the top left corner of the region.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    x0: usize,
+    y0: usize,
+    len_x: usize,
+    len_y: usize,
+}
+
+// derive would require `T: Clone`
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    /// returns a view of a region of this view, see `Grid::view`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region does not fit inside the view.
+    pub fn view(&self, x: usize, y: usize, len_x: usize, len_y: usize) -> GridView<'a, T> {
+        assert!(
+            x + len_x <= self.len_x && y + len_y <= self.len_y,
+            "region {}x{} at ({}, {}) does not fit in a {}x{} view",
+            len_x,
+            len_y,
+            x,
+            y,
+            self.len_x,
+            self.len_y
+        );
+        GridView {
+            grid: self.grid,
+            x0: self.x0 + x,
+            y0: self.y0 + y,
+            len_x,
+            len_y,
+        }
+    }
+
+    /// coordinates of the top left corner of the view in the grid.
+    pub fn offset(&self) -> (usize, usize) {
+        (self.x0, self.y0)
+    }
+
+    /// number of columns of the view.
+    pub fn len_x(&self) -> usize {
+        self.len_x
+    }
+
+    /// number of rows of the view.
+    pub fn len_y(&self) -> usize {
+        self.len_y
+    }
+
+    /// returns the total size of the view (len_x * len_y)
+    pub fn size(&self) -> usize {
+        self.len_x * self.len_y
+    }
+
+    /// returns the value at position x,y of the view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of the view.
+    pub fn get(&self, x: usize, y: usize) -> &'a T {
+        assert!(x < self.len_x && y < self.len_y, "({}, {}) is out of the view", x, y);
+        self.grid.get(self.x0 + x, self.y0 + y)
+    }
+
+    /// returns the value at position `pos` of the view, or `None` if it is out of the view.
+    pub fn try_get(&self, pos: Base2d<i64>) -> Option<&'a T> {
+        let (x, y) = checked_coords(self.len_x, self.len_y, pos)?;
+        Some(self.grid.get(self.x0 + x, self.y0 + y))
+    }
+
+    /// returns an iterator over the values of the view in row major order.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        let view = *self;
+        (0..self.len_y).flat_map(move |y| view.row(y).iter())
+    }
+
+    /// returns an array slice for a line of the view.
+    pub fn row(&self, y: usize) -> &'a [T] {
+        assert!(y < self.len_y, "row {} is out of the view", y);
+        let start = self.grid.index(self.x0, self.y0 + y);
+        &self.grid.flat[start..start + self.len_x]
+    }
+
+    pub fn iter_col(&self, x: usize) -> impl DoubleEndedIterator<Item = &'a T> + ExactSizeIterator {
+        assert!(x < self.len_x, "column {} is out of the view", x);
+        let start = self.grid.index(self.x0 + x, self.y0);
+        let end = if self.len_y == 0 {
+            start
+        } else {
+            start + (self.len_y - 1) * self.grid.len_x + 1
+        };
+        self.grid.flat[start..end].iter().step_by(self.grid.len_x)
+    }
+
+    /// see `Grid::neighbors4_coords`, cells out of the view are skipped.
+    pub fn neighbors4_coords(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize)> {
+        neighbor_coords(self.len_x, self.len_y, pos.into(), &NEIGHBORS4)
+    }
+
+    /// see `Grid::neighbors8_coords`, cells out of the view are skipped.
+    pub fn neighbors8_coords(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize)> {
+        neighbor_coords(self.len_x, self.len_y, pos.into(), &NEIGHBORS8)
+    }
+
+    /// see `Grid::neighbors4`, cells out of the view are skipped.
+    pub fn neighbors4(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &'a T)> {
+        let view = *self;
+        self.neighbors4_coords(pos).map(move |(x, y)| (x, y, view.get(x, y)))
+    }
+
+    /// see `Grid::neighbors8`, cells out of the view are skipped.
+    pub fn neighbors8(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &'a T)> {
+        let view = *self;
+        self.neighbors8_coords(pos).map(move |(x, y)| (x, y, view.get(x, y)))
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// copies the region into a new grid.
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            flat: self.iter().cloned().collect(),
+            len_x: self.len_x,
+            len_y: self.len_y,
+            layout: PhantomData,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+    }
+}
+
+//--------------------------------------------------------------------
+// Directions
+//--------------------------------------------------------------------
+
+/// The 4 cardinal and the 4 diagonal directions. North points to the first row (`y` decreasing) and west to the first
+/// column (`x` decreasing).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// the 4 cardinal directions, clockwise from north.
+    pub const CARDINAL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    /// all the 8 directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// returns the `(dx, dy)` of a single step towards the direction.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+}
+
+//--------------------------------------------------------------------
+// Indexing
+//--------------------------------------------------------------------
+
+impl<T, L: Layout> Index<(usize, usize)> for Grid<T, L> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+    }
+}
+
+impl<T, L: Layout> IndexMut<(usize, usize)> for Grid<T, L> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(x, y)
+    }
+}
+
+impl<T, L: Layout> Index<Base2d<usize>> for Grid<T, L> {
+    type Output = T;
+
+    fn index(&self, pos: Base2d<usize>) -> &Self::Output {
+        self.get(pos.x, pos.y)
+    }
+}
+
+impl<T, L: Layout> IndexMut<Base2d<usize>> for Grid<T, L> {
+    fn index_mut(&mut self, pos: Base2d<usize>) -> &mut Self::Output {
+        self.get_mut(pos.x, pos.y)
+    }
+}
+
+//--------------------------------------------------------------------
+// Errors
+//--------------------------------------------------------------------
+
+/// Error returned when building a grid from a vector with less elements than the grid size.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GridSizeError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for GridSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "not enough elements for the grid: expected at least {}, found {}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl Error for GridSizeError {}
+
+/// Error returned when building a grid from a character map. Rows and columns are zero based, as the grid coordinates.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseGridError<E> {
+    /// The input has no cells.
+    Empty,
+    /// The row `row` has `found` cells, while the first row has `expected`. `col` is the first column where they differ.
+    Ragged {
+        row: usize,
+        col: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The cell conversion failed for the character at `row` and `col`.
+    Cell { row: usize, col: usize, error: E },
+}
+
+impl<E: fmt::Display> fmt::Display for ParseGridError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Empty => write!(f, "empty grid"),
+            ParseGridError::Ragged {
+                row,
+                col,
+                expected,
+                found,
+            } => write!(
+                f,
+                "ragged row {} at column {}: expected {} cells, found {}",
+                row, col, expected, found
+            ),
+            ParseGridError::Cell { row, col, error } => {
+                write!(f, "invalid cell at row {}, column {}: {}", row, col, error)
+            }
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> Error for ParseGridError<E> {}
+
+//--------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digit(c: char) -> Result<u32, char> {
+        c.to_digit(10).ok_or(c)
+    }
+
+    #[test]
+    fn teste_parse() {
+        let grid = Grid::parse("123\n456\n", digit).unwrap();
+        assert_eq!((grid.len_x, grid.len_y), (3, 2));
+        assert_eq!(*grid.get(2, 1), 6);
+        assert_eq!(grid.row(0), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn teste_parse_errors() {
+        assert_eq!(Grid::parse("", digit), Err(ParseGridError::Empty));
+        assert_eq!(Grid::parse("\n\n", digit), Err(ParseGridError::Empty));
+        assert_eq!(
+            Grid::parse("123\n45\n678", digit),
+            Err(ParseGridError::Ragged {
+                row: 1,
+                col: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Grid::parse("123\n4x6", digit),
+            Err(ParseGridError::Cell {
+                row: 1,
+                col: 1,
+                error: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn teste_index() {
+        let mut grid = Grid::from_vec(3, 2, vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(grid.size(), 6);
+        assert_eq!(grid[(2, 1)], 5);
+        assert_eq!(grid[Base2d::new(1, 0)], 1);
+        grid[(0, 1)] = 30;
+        grid[Base2d::new(1, 1)] += 10;
+        assert_eq!(grid.row(1), &[30, 14, 5]);
+
+        assert_eq!(grid.try_get(Base2d::new(2, 1)), Some(&5));
+        assert_eq!(grid.try_get(Base2d::new(3, 0)), None);
+        assert_eq!(grid.try_get(Base2d::new(0, -1)), None);
+        assert_eq!(grid.try_get(Base2d::new(i64::MIN, i64::MAX)), None);
+        *grid.try_get_mut(Base2d::new(0, 0)).unwrap() = 7;
+        assert_eq!(grid[(0, 0)], 7);
+        assert!(grid.try_get_mut(Base2d::new(0, 2)).is_none());
+    }
+
+    #[test]
+    fn teste_indexed() {
+        let mut grid = Grid::from_vec(3, 2, vec![0, 1, 2, 3, 4, 5]);
+        let cells: Vec<_> = grid.iter_indexed().filter(|(_, _, &v)| v % 2 == 1).collect();
+        assert_eq!(cells, vec![(1, 0, &1), (0, 1, &3), (2, 1, &5)]);
+        grid.iter_indexed_mut().for_each(|(x, y, v)| *v += x * 10 + y * 100);
+        assert_eq!(grid.row(1), &[103, 114, 125]);
+
+        let halves = grid.map(|&v| v as f32 / 2.0);
+        assert_eq!(halves[(1, 0)], 5.5);
+        let sums = grid.zip_with(&halves, |&a, &b| a as f32 + b);
+        assert_eq!(sums[(2, 1)], 187.5);
+
+        assert_eq!(grid.position(|&v| v > 100), Some((0, 1)));
+        assert_eq!(grid.find(|&v| v > 120), Some((2, 1, &125)));
+        assert_eq!(grid.position(|&v| v > 1000), None);
+    }
+
+    #[test]
+    fn teste_try_from_vec() {
+        assert_eq!(
+            Grid::try_from_vec(3, 3, vec![0; 8]),
+            Err(GridSizeError { expected: 9, found: 8 })
+        );
+        assert!(Grid::try_from_vec(3, 3, vec![0; 9]).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "position (3, 0) is out of bounds for a 3x2 grid")]
+    fn teste_index_x_out_of_bounds() {
+        let _ = Grid::from_vec(3, 2, vec![0, 1, 2, 3, 4, 5])[(3, 0)];
+    }
+
+    #[test]
+    #[should_panic]
+    fn teste_from_vec_short() {
+        Grid::from_vec(2, 2, vec![0; 3]);
+    }
+
+    #[test]
+    fn teste_neighbors() {
+        let mut grid = Grid::parse("123\n456\n789", digit).unwrap();
+
+        let corner: Vec<_> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(corner, vec![(1, 0, &2), (0, 1, &4)]);
+
+        let center: Vec<u32> = grid.neighbors8(Base2d::new(1, 1)).map(|(_, _, &v)| v).collect();
+        assert_eq!(center, vec![1, 2, 3, 4, 6, 7, 8, 9]);
+
+        let edge: Vec<_> = grid.neighbors8_coords((2, 1)).collect();
+        assert_eq!(edge, vec![(1, 0), (2, 0), (1, 1), (1, 2), (2, 2)]);
+
+        grid.neighbors4_mut((1, 1)).for_each(|(_, _, v)| *v = 0);
+        assert_eq!(
+            grid.iter().copied().collect::<Vec<_>>(),
+            vec![1, 0, 3, 0, 5, 0, 7, 0, 9]
+        );
+        grid.neighbors8_mut((2, 2))
+            .for_each(|(x, y, v)| *v = (x + y) as u32 * 10);
+        assert_eq!(grid.row(1), &[0, 20, 30]);
+        assert_eq!(grid.row(2), &[7, 30, 9]);
+    }
+
+    #[test]
+    fn teste_ray() {
+        let grid = Grid::parse("123\n456\n789", digit).unwrap();
+
+        let east: Vec<_> = grid.ray((0, 1), Direction::East).collect();
+        assert_eq!(east, vec![(1, 1, &5), (2, 1, &6)]);
+        let north: Vec<_> = grid.ray_coords(Base2d::new(1, 2), Direction::North).collect();
+        assert_eq!(north, vec![(1, 1), (1, 0)]);
+        let diagonal: Vec<u32> = grid.ray((2, 2), Direction::NorthWest).map(|(_, _, &v)| v).collect();
+        assert_eq!(diagonal, vec![5, 1]);
+        assert_eq!(grid.ray((0, 0), Direction::SouthWest).count(), 0);
+        assert_eq!(grid.ray((0, 2), Direction::NorthEast).count(), 2);
+
+        assert_eq!(grid.look((0, 0), Direction::East, |&v| v >= 2), 1);
+        assert_eq!(grid.look((0, 0), Direction::South, |&v| v >= 9), 2);
+        assert_eq!(grid.look((2, 0), Direction::West, |&v| v >= 5), 2);
+    }
+
+    #[test]
+    fn teste_transforms() {
+        let grid = Grid::parse("123\n456", digit).unwrap();
+        let rows = |g: &Grid<u32>| -> Vec<Vec<u32>> { (0..g.len_y).map(|y| g.row(y).to_vec()).collect() };
+
+        assert_eq!(rows(&grid.transpose()), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(rows(&grid.rotate_cw()), vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+        assert_eq!(rows(&grid.rotate_ccw()), vec![vec![3, 6], vec![2, 5], vec![1, 4]]);
+        assert_eq!(rows(&grid.flip_x()), vec![vec![3, 2, 1], vec![6, 5, 4]]);
+        assert_eq!(rows(&grid.flip_y()), vec![vec![4, 5, 6], vec![1, 2, 3]]);
+
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.rotate_cw().rotate_cw(), grid.flip_x().flip_y());
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn teste_view() {
+        let grid = Grid::parse("1234\n5678\n9abc", |c| c.to_digit(16).ok_or(c)).unwrap();
+        let view = grid.view(1, 1, 3, 2);
+
+        assert_eq!((view.len_x(), view.len_y(), view.size()), (3, 2, 6));
+        assert_eq!(*view.get(0, 0), 6);
+        assert_eq!(view[(2, 1)], 12);
+        assert_eq!(view.row(1), &[10, 11, 12]);
+        assert_eq!(view.iter_col(1).rev().copied().collect::<Vec<_>>(), vec![11, 7]);
+        assert_eq!(view.iter().copied().collect::<Vec<_>>(), vec![6, 7, 8, 10, 11, 12]);
+        assert_eq!(view.try_get(Base2d::new(3, 0)), None);
+
+        let neighbors: Vec<u32> = view.neighbors8((0, 0)).map(|(_, _, &v)| v).collect();
+        assert_eq!(neighbors, vec![7, 10, 11]);
+        assert_eq!(view.neighbors4_coords((2, 1)).collect::<Vec<_>>(), vec![(2, 0), (1, 1)]);
+
+        let inner = view.view(1, 0, 2, 2);
+        assert_eq!(inner.to_grid(), Grid::from_vec(2, 2, vec![7, 8, 11, 12]));
+        assert_eq!(grid.as_view().to_grid(), grid);
+    }
+
+    #[test]
+    #[should_panic]
+    fn teste_view_out_of_bounds() {
+        Grid::new(3, 3, 0).view(2, 0, 2, 1);
+    }
+
+    #[test]
+    fn teste_layouts() {
+        fn check<L: Layout>(grid: Grid<u32>) {
+            let mut other: Grid<u32, L> = grid.clone().to_layout();
+            assert_eq!(other.to_text(|&v| char::from_digit(v, 10).unwrap()), grid.to_string());
+            assert_eq!(
+                other.iter_col(1).collect::<Vec<_>>(),
+                grid.iter_col(1).collect::<Vec<_>>()
+            );
+            assert_eq!(other.iter_row(2).rev().collect::<Vec<_>>(), vec![&9, &8, &7]);
+            assert_eq!(other.position(|&v| v == 6), Some((2, 1)));
+
+            // the order does not depend on the layout
+            assert_eq!(other.position(|&v| v % 2 == 0), Some((1, 0)));
+            assert_eq!(other.find(|&v| v > 3), Some((0, 1, &4)));
+            let values: Vec<u32> = other.iter_indexed().map(|(_, _, &v)| v).collect();
+            assert_eq!(values, (1..=9).collect::<Vec<_>>());
+            let mut order = Vec::new();
+            let doubled = other.map(|&v| {
+                order.push(v);
+                v * 2
+            });
+            assert_eq!(order, values);
+            assert_eq!(doubled.to_layout::<RowMajor>(), grid.map(|&v| v * 2));
+            let changed = other.map(|&v| v % 4);
+            let diffs: Vec<_> = other.diff(&changed).iter().map(|d| (d.x, d.y)).collect();
+            assert_eq!(diffs, vec![(0, 1), (1, 1), (2, 1), (0, 2), (1, 2), (2, 2)]);
+            other.iter_indexed_mut().zip(0..).for_each(|((x, y, v), i)| {
+                assert_eq!(x + 3 * y, i);
+                *v = i as u32 + 1;
+            });
+            assert_eq!(other.to_layout::<RowMajor>(), grid);
+            let mut other: Grid<u32, L> = grid.clone().to_layout();
+
+            assert_eq!(other.rotate_cw().to_layout::<RowMajor>(), grid.rotate_cw());
+
+            other.iter_col_mut(0).rev().zip(1..).for_each(|(v, i)| *v *= i);
+            other.iter_row_mut(0).skip(1).for_each(|v| *v = 0);
+            for (_, _, v) in other.neighbors8_mut((1, 1)) {
+                *v += 1;
+            }
+            let neighbors: Vec<_> = other.neighbors4((1, 1)).map(|(x, y, &v)| (x, y, v)).collect();
+            assert_eq!(neighbors, vec![(1, 0, 1), (0, 1, 9), (2, 1, 7), (1, 2, 9)]);
+            assert_eq!(other.to_layout::<RowMajor>().row(0), &[4, 1, 1]);
+        }
+
+        let grid = Grid::parse("123\n456\n789", digit).unwrap();
+        check::<RowMajor>(grid.clone());
+        check::<ColMajor>(grid.clone());
+        check::<Tiled<2>>(grid);
+    }
+
+    #[test]
+    #[should_panic(expected = "position (0, 2) is out of bounds for a 3x2 grid")]
+    fn teste_col_major_out_of_bounds() {
+        let grid: Grid<u32, ColMajor> = Grid::from_vec(3, 2, vec![0, 1, 2, 3, 4, 5]).to_layout();
+        let _ = grid[(0, 2)];
+    }
+
+    #[test]
+    fn teste_parse_with_markers() {
+        let (grid, markers) = Grid::parse_with_markers("Sab\nxyE", &['S', 'E'], |c| match c {
+            'S' => Ok::<_, ()>('a'),
+            'E' => Ok('z'),
+            c => Ok(c),
+        })
+        .unwrap();
+        assert_eq!(*grid.get(0, 0), 'a');
+        assert_eq!(*grid.get(2, 1), 'z');
+        assert_eq!(markers, vec![('S', Base2d::new(0, 0)), ('E', Base2d::new(2, 1))]);
     }
 }
@@ -0,0 +1,184 @@
+//! A trait over the dense and bounded grids, so algorithms may be written once for both `Grid` and `FixedGrid`.
+//!
+//! Implementors only provide the dimensions, single cell access and iteration over the values in storage order. The
+//! other methods are provided on top of those, they visit the cells in row major order whatever the storage. The
+//! inherent methods of `Grid` with the same names give the same results: most of them forward to the provided ones,
+//! while `iter_indexed`, `position` and `find` are faster on `Grid`, which overrides them with its own.
+//!
+//! ```
+//! use aoc2022::helpers::{fixed_grid::FixedGrid, grid::{Board, Grid}};
+//!
+//! fn count_walls<B: Board<Cell = char>>(board: &B) -> usize {
+//!     board.iter().filter(|&&c| c == '#').count()
+//! }
+//!
+//! let grid = Grid::parse("#..\n.##", |c| Ok::<_, ()>(c)).unwrap();
+//! let fixed: FixedGrid<char, 3, 2> = FixedGrid::from([['#', '.', '.'], ['.', '#', '#']]);
+//! assert_eq!(count_walls(&grid), count_walls(&fixed));
+//! ```
+
+use super::{
+    assert_col, assert_row, checked_coords, count_seen, neighbor_coords, ray_coords, Base2d, Direction, Grid, Layout,
+    NEIGHBORS4, NEIGHBORS8,
+};
+
+/// Read and write access to the cells of a dense, bounded two-dimensional grid.
+pub trait Board {
+    type Cell;
+
+    /// number of columns, the length of each row.
+    fn len_x(&self) -> usize;
+
+    /// number of rows, the length of each column.
+    fn len_y(&self) -> usize;
+
+    /// returns the value at position x,y.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    fn get(&self, x: usize, y: usize) -> &Self::Cell;
+
+    fn get_mut(&mut self, x: usize, y: usize) -> &mut Self::Cell;
+
+    /// returns an iterator over the values in storage order.
+    fn iter(&self) -> impl Iterator<Item = &Self::Cell>;
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut Self::Cell>;
+
+    //------------------------------
+    // Provided
+    //------------------------------
+
+    /// returns the total number of cells (len_x * len_y)
+    fn size(&self) -> usize {
+        self.len_x() * self.len_y()
+    }
+
+    /// returns the value at position `pos`, or `None` if it is out of bounds.
+    fn try_get(&self, pos: Base2d<i64>) -> Option<&Self::Cell> {
+        let (x, y) = checked_coords(self.len_x(), self.len_y(), pos)?;
+        Some(self.get(x, y))
+    }
+
+    fn try_get_mut(&mut self, pos: Base2d<i64>) -> Option<&mut Self::Cell> {
+        let (x, y) = checked_coords(self.len_x(), self.len_y(), pos)?;
+        Some(self.get_mut(x, y))
+    }
+
+    /// returns an iterator over every cell along with its coordinates, in row major order.
+    fn iter_indexed(&self) -> impl Iterator<Item = (usize, usize, &Self::Cell)> {
+        (0..self.len_y()).flat_map(move |y| (0..self.len_x()).map(move |x| (x, y, self.get(x, y))))
+    }
+
+    /// returns an iterator over the values of row `y`, from left to right.
+    fn iter_row(&self, y: usize) -> impl DoubleEndedIterator<Item = &Self::Cell> + ExactSizeIterator {
+        assert_row(y, self.len_y());
+        (0..self.len_x()).map(move |x| self.get(x, y))
+    }
+
+    /// returns an iterator over the values of column `x`, from top to bottom.
+    fn iter_col(&self, x: usize) -> impl DoubleEndedIterator<Item = &Self::Cell> + ExactSizeIterator {
+        assert_col(x, self.len_x());
+        (0..self.len_y()).map(move |y| self.get(x, y))
+    }
+
+    /// see `Grid::neighbors4_coords`.
+    fn neighbors4_coords(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize)> {
+        neighbor_coords(self.len_x(), self.len_y(), pos.into(), &NEIGHBORS4)
+    }
+
+    /// see `Grid::neighbors8_coords`.
+    fn neighbors8_coords(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize)> {
+        neighbor_coords(self.len_x(), self.len_y(), pos.into(), &NEIGHBORS8)
+    }
+
+    /// see `Grid::neighbors4`.
+    fn neighbors4(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &Self::Cell)> {
+        self.neighbors4_coords(pos).map(move |(x, y)| (x, y, self.get(x, y)))
+    }
+
+    /// see `Grid::neighbors8`.
+    fn neighbors8(&self, pos: impl Into<(usize, usize)>) -> impl Iterator<Item = (usize, usize, &Self::Cell)> {
+        self.neighbors8_coords(pos).map(move |(x, y)| (x, y, self.get(x, y)))
+    }
+
+    /// returns the coordinates of the first cell, in row major order, whose value matches `pred`.
+    fn position<P>(&self, pred: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&Self::Cell) -> bool,
+    {
+        self.find(pred).map(|(x, y, _)| (x, y))
+    }
+
+    /// returns the first cell, in row major order, whose value matches `pred`, along with its coordinates.
+    fn find<P>(&self, mut pred: P) -> Option<(usize, usize, &Self::Cell)>
+    where
+        P: FnMut(&Self::Cell) -> bool,
+    {
+        self.iter_indexed().find(|(_, _, v)| pred(v))
+    }
+
+    /// see `Grid::ray`.
+    fn ray(
+        &self,
+        pos: impl Into<(usize, usize)>,
+        direction: Direction,
+    ) -> impl Iterator<Item = (usize, usize, &Self::Cell)> {
+        ray_coords(self.len_x(), self.len_y(), pos.into(), direction).map(move |(x, y)| (x, y, self.get(x, y)))
+    }
+
+    /// see `Grid::look`.
+    fn look<F>(&self, pos: impl Into<(usize, usize)>, direction: Direction, is_blocking: F) -> usize
+    where
+        F: FnMut(&Self::Cell) -> bool,
+    {
+        count_seen(self.ray(pos, direction).map(|(_, _, v)| v), is_blocking)
+    }
+}
+
+impl<T, L: Layout> Board for Grid<T, L> {
+    type Cell = T;
+
+    fn len_x(&self) -> usize {
+        self.len_x
+    }
+
+    fn len_y(&self) -> usize {
+        self.len_y
+    }
+
+    fn get(&self, x: usize, y: usize) -> &T {
+        Grid::get(self, x, y)
+    }
+
+    fn get_mut(&mut self, x: usize, y: usize) -> &mut T {
+        Grid::get_mut(self, x, y)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        self.flat.iter()
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.flat.iter_mut()
+    }
+
+    fn iter_indexed(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        Grid::iter_indexed(self)
+    }
+
+    fn position<P>(&self, pred: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        Grid::position(self, pred)
+    }
+
+    fn find<P>(&self, pred: P) -> Option<(usize, usize, &T)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        Grid::find(self, pred)
+    }
+}
@@ -0,0 +1,199 @@
+//! Comparing grids: the cells that changed, side by side rendering and snapshot assertions for tests.
+//!
+//! ```
+//! use aoc2022::helpers::grid::Grid;
+//! let grid = Grid::parse("#..\n.#.", |c| Ok::<_, ()>(c == '#')).unwrap();
+//! grid.assert_snapshot(
+//!     "#..
+//! .#.",
+//!     |&v| if v { '#' } else { '.' },
+//! );
+//! ```
+
+use std::fmt::Write;
+
+use super::{Grid, Layout};
+
+/// maximum number of mismatching cells listed by a failed snapshot assertion.
+const MAX_REPORTED: usize = 20;
+
+/// A cell holding different values in two grids.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CellDiff<'a, T> {
+    pub x: usize,
+    pub y: usize,
+    pub old: &'a T,
+    pub new: &'a T,
+}
+
+impl<T: PartialEq, L: Layout> Grid<T, L> {
+    /// returns the cells whose values differ between `self` (old) and `other` (new), in row major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grids have different dimensions.
+    pub fn diff<'a>(&'a self, other: &'a Grid<T, L>) -> Vec<CellDiff<'a, T>> {
+        assert!(
+            self.len_x == other.len_x && self.len_y == other.len_y,
+            "cannot diff a {}x{} grid with a {}x{} one",
+            self.len_x,
+            self.len_y,
+            other.len_x,
+            other.len_y
+        );
+        self.iter_indexed()
+            .zip(other.iter_indexed())
+            .filter(|((_, _, old), (_, _, new))| old != new)
+            .map(|((x, y, old), (_, _, new))| CellDiff { x, y, old, new })
+            .collect()
+    }
+}
+
+impl<T, L: Layout> Grid<T, L> {
+    /// renders `self` and `other` next to each other, with each cell mapped to a char by `f`. Rows where the renderings
+    /// differ are marked with a `<` at the end.
+    pub fn side_by_side<F>(&self, other: &Grid<T, L>, f: F) -> String
+    where
+        F: Fn(&T) -> char,
+    {
+        let left = self.to_text(&f);
+        let right = other.to_text(&f);
+        side_by_side(&left, &right)
+    }
+
+    /// compares the grid rendered with `f` against the `expected` text, one line per row. Returns a report with the
+    /// coordinates of the mismatching cells and both renderings side by side if they differ.
+    pub fn check_snapshot<F>(&self, expected: &str, f: F) -> Result<(), String>
+    where
+        F: Fn(&T) -> char,
+    {
+        let actual = self.to_text(&f);
+        let expected = expected.trim_end_matches(['\n', '\r']);
+        if actual == expected {
+            return Ok(());
+        }
+
+        let mut report = String::new();
+        let expected_rows: Vec<Vec<char>> = expected.lines().map(|l| l.chars().collect()).collect();
+        let expected_len_x = expected_rows.first().map_or(0, Vec::len);
+        if expected_rows.len() != self.len_y || expected_rows.iter().any(|r| r.len() != self.len_x) {
+            writeln!(
+                report,
+                "dimensions differ: expected {}x{}, found {}x{}",
+                expected_len_x,
+                expected_rows.len(),
+                self.len_x,
+                self.len_y
+            )
+            .unwrap();
+        }
+
+        let mismatches: Vec<(usize, usize, Option<char>, char)> = (0..self.len_y)
+            .flat_map(|y| (0..self.len_x).map(move |x| (x, y)))
+            .filter_map(|(x, y)| {
+                let found = f(self.get(x, y));
+                let expected = expected_rows.get(y).and_then(|r| r.get(x)).copied();
+                (expected != Some(found)).then_some((x, y, expected, found))
+            })
+            .collect();
+        for &(x, y, expected, found) in mismatches.iter().take(MAX_REPORTED) {
+            match expected {
+                Some(c) => writeln!(report, "({}, {}): expected '{}', found '{}'", x, y, c, found),
+                None => writeln!(report, "({}, {}): not expected, found '{}'", x, y, found),
+            }
+            .unwrap();
+        }
+        if mismatches.len() > MAX_REPORTED {
+            writeln!(report, "... and {} more", mismatches.len() - MAX_REPORTED).unwrap();
+        }
+
+        writeln!(report, "\nexpected | found\n{}", side_by_side(expected, &actual)).unwrap();
+        Err(report)
+    }
+
+    /// panics with the report of `check_snapshot` if the grid does not match the `expected` text.
+    #[track_caller]
+    pub fn assert_snapshot<F>(&self, expected: &str, f: F)
+    where
+        F: Fn(&T) -> char,
+    {
+        if let Err(report) = self.check_snapshot(expected, f) {
+            panic!("grid does not match the snapshot\n{}", report);
+        }
+    }
+}
+
+/// joins two multi-line texts as columns, marking the lines that differ.
+fn side_by_side(left: &str, right: &str) -> String {
+    let left: Vec<&str> = left.lines().collect();
+    let right: Vec<&str> = right.lines().collect();
+    let width = left.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    let mut s = String::new();
+    for i in 0..left.len().max(right.len()) {
+        let l = left.get(i).copied().unwrap_or("");
+        let r = right.get(i).copied().unwrap_or("");
+        let marker = if l != r { " <" } else { "" };
+        writeln!(s, "{:width$} | {}{}", l, r, marker, width = width).unwrap();
+    }
+    s.pop(); // last new line
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_char(v: &u32) -> char {
+        char::from_digit(*v, 10).unwrap()
+    }
+
+    #[test]
+    fn teste_diff() {
+        let old = Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]);
+        let mut new = old.clone();
+        new[(2, 0)] = 0;
+        new[(1, 1)] = 9;
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff,
+            vec![
+                CellDiff {
+                    x: 2,
+                    y: 0,
+                    old: &3,
+                    new: &0
+                },
+                CellDiff {
+                    x: 1,
+                    y: 1,
+                    old: &5,
+                    new: &9
+                },
+            ]
+        );
+        assert!(old.diff(&old).is_empty());
+        assert_eq!(old.side_by_side(&new, to_char), "123 | 120 <\n456 | 496 <");
+    }
+
+    #[test]
+    fn teste_snapshot() {
+        let grid = Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(grid.check_snapshot("123\n456\n", to_char), Ok(()));
+
+        let report = grid.check_snapshot("123\n406", to_char).unwrap_err();
+        assert!(report.starts_with("(1, 1): expected '0', found '5'\n"));
+        assert!(report.ends_with("123 | 123\n406 | 456 <\n"));
+
+        let report = grid.check_snapshot("12\n45\n78", to_char).unwrap_err();
+        assert!(report.starts_with("dimensions differ: expected 2x3, found 3x2\n"));
+        assert!(report.contains("(2, 0): not expected, found '3'"));
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 0): expected '7', found '1'")]
+    fn teste_assert_snapshot() {
+        Grid::from_vec(1, 1, vec![1]).assert_snapshot("7", to_char);
+    }
+}
@@ -0,0 +1,237 @@
+//! Storage layouts: the order in which the cells of a grid are kept in memory.
+//!
+//! The default `RowMajor` layout keeps each row contiguous, which suits scans along the rows. `ColMajor` does the same
+//! for the columns, and `Tiled` stores square blocks of `SIDE` by `SIDE` cells one after the other, so walks towards
+//! any direction stay within a few cache lines. The layout is the second type parameter of `Grid`, and a grid is moved
+//! to another layout with `to_layout`. The layout does not change any result, the cells are visited in row major order
+//! with any of them, except by `iter` and `iter_mut`, which follow the storage order:
+//!
+//! ```
+//! use aoc2022::helpers::grid::{ColMajor, Grid, Tiled};
+//! let grid = Grid::parse("123\n456", |c| c.to_digit(10).ok_or(c)).unwrap();
+//!
+//! let by_cols: Grid<u32, ColMajor> = grid.clone().to_layout();
+//! assert_eq!(by_cols.iter().copied().collect::<Vec<_>>(), vec![1, 4, 2, 5, 3, 6]);
+//! assert_eq!(by_cols[(2, 1)], grid[(2, 1)]);
+//! assert_eq!(by_cols.position(|&v| v % 2 == 0), Some((1, 0)));
+//!
+//! let tiled = grid.to_layout::<Tiled<2>>();
+//! assert_eq!(tiled.iter_col(2).collect::<Vec<_>>(), vec![&3, &6]);
+//! ```
+
+use std::{fmt, iter::FusedIterator};
+
+/// Maps the coordinates of a cell to its position in the storage of a grid. The layouts are zero sized markers, the
+/// supertraits let `Grid` derive its traits for any layout.
+///
+/// The accessors, iterators and algorithms of `Grid` are available with every layout and give the same results. The
+/// ones needing contiguous rows only exist for `Grid<T, RowMajor>`: parsing, row slices (`row`, `row_mut`, `rows`,
+/// `rows_mut`, `into_vec`), views, windows, resizing and `Automaton`. Move the grid with `to_layout` to use them.
+pub trait Layout: Copy + Eq + fmt::Debug {
+    /// tells if `index` is the row major order itself, which lets the grid skip reordering the cells.
+    const ROW_MAJOR: bool = false;
+
+    /// returns the position in storage of the cell at `(x, y)` of a `len_x` by `len_y` grid. The mapping must be one
+    /// to one onto `0..len_x * len_y`, and the position must grow along each row and along each column.
+    fn index(x: usize, y: usize, len_x: usize, len_y: usize) -> usize;
+
+    /// the inverse of `index`, returns the coordinates of the cell stored at position `i`.
+    fn coords(i: usize, len_x: usize, len_y: usize) -> (usize, usize);
+}
+
+/// Rows one after the other, the default layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RowMajor;
+
+impl Layout for RowMajor {
+    const ROW_MAJOR: bool = true;
+
+    #[inline]
+    fn index(x: usize, y: usize, len_x: usize, _: usize) -> usize {
+        len_x * y + x
+    }
+
+    #[inline]
+    fn coords(i: usize, len_x: usize, _: usize) -> (usize, usize) {
+        (i % len_x, i / len_x)
+    }
+}
+
+/// Columns one after the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ColMajor;
+
+impl Layout for ColMajor {
+    #[inline]
+    fn index(x: usize, y: usize, _: usize, len_y: usize) -> usize {
+        len_y * x + y
+    }
+
+    #[inline]
+    fn coords(i: usize, _: usize, len_y: usize) -> (usize, usize) {
+        (i / len_y, i % len_y)
+    }
+}
+
+/// Square tiles of `SIDE` by `SIDE` cells, in row major order, each one stored in row major order. The tiles on the
+/// right and bottom edges are cut short when the grid dimensions are not multiples of `SIDE`, so no space is wasted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Tiled<const SIDE: usize>;
+
+impl<const SIDE: usize> Layout for Tiled<SIDE> {
+    #[inline]
+    fn index(x: usize, y: usize, len_x: usize, len_y: usize) -> usize {
+        const { assert!(SIDE > 0, "tiles must not be empty") };
+        let (tx, ty) = (x / SIDE, y / SIDE);
+        // the tiles before this one are full, only this one may be cut short
+        let tile_w = SIDE.min(len_x - tx * SIDE);
+        let tile_h = SIDE.min(len_y - ty * SIDE);
+        ty * SIDE * len_x + tx * SIDE * tile_h + (y % SIDE) * tile_w + x % SIDE
+    }
+
+    #[inline]
+    fn coords(i: usize, len_x: usize, len_y: usize) -> (usize, usize) {
+        const { assert!(SIDE > 0, "tiles must not be empty") };
+        let ty = i / (SIDE * len_x);
+        let tile_h = SIDE.min(len_y - ty * SIDE);
+        let i = i % (SIDE * len_x);
+        let tx = i / (SIDE * tile_h);
+        let tile_w = SIDE.min(len_x - tx * SIDE);
+        let i = i % (SIDE * tile_h);
+        (tx * SIDE + i % tile_w, ty * SIDE + i / tile_w)
+    }
+}
+
+/// Mutable references to the cells stored at `indices`, which must be strictly increasing. The storage is split as
+/// the iterator advances from either end, so each cell is borrowed only once.
+pub(super) struct CellsMut<'a, T, I> {
+    rest: &'a mut [T],
+    /// storage position of `rest[0]`
+    offset: usize,
+    indices: I,
+}
+
+impl<'a, T, I> CellsMut<'a, T, I> {
+    pub(super) fn new(flat: &'a mut [T], indices: I) -> Self {
+        CellsMut {
+            rest: flat,
+            offset: 0,
+            indices,
+        }
+    }
+}
+
+impl<'a, T, I: Iterator<Item = usize>> Iterator for CellsMut<'a, T, I> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        let i = self.indices.next()?;
+        let (cell, tail) = std::mem::take(&mut self.rest)[i - self.offset..].split_first_mut()?;
+        self.rest = tail;
+        self.offset = i + 1;
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<T, I: DoubleEndedIterator<Item = usize>> DoubleEndedIterator for CellsMut<'_, T, I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let i = self.indices.next_back()?;
+        let (cell, head) = std::mem::take(&mut self.rest)[..=i - self.offset].split_last_mut()?;
+        self.rest = head;
+        Some(cell)
+    }
+}
+
+impl<T, I: ExactSizeIterator<Item = usize>> ExactSizeIterator for CellsMut<'_, T, I> {}
+
+impl<T, I: FusedIterator<Item = usize>> FusedIterator for CellsMut<'_, T, I> {}
+
+/// One of two iterators over the same items, so a method may take a fast path for some layouts and still return a
+/// single type.
+pub(super) enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+impl<A: Iterator, B: Iterator<Item = A::Item>> Iterator for Either<A, B> {
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Either::Left(a) => a.next(),
+            Either::Right(b) => b.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Either::Left(a) => a.size_hint(),
+            Either::Right(b) => b.size_hint(),
+        }
+    }
+}
+
+impl<A: DoubleEndedIterator, B: DoubleEndedIterator<Item = A::Item>> DoubleEndedIterator for Either<A, B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            Either::Left(a) => a.next_back(),
+            Either::Right(b) => b.next_back(),
+        }
+    }
+}
+
+impl<A: ExactSizeIterator, B: ExactSizeIterator<Item = A::Item>> ExactSizeIterator for Either<A, B> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// checks that `L` maps the cells one to one and that `coords` is the inverse of `index`.
+    fn check_layout<L: Layout>(len_x: usize, len_y: usize) -> Vec<usize> {
+        let indices: Vec<usize> = (0..len_y)
+            .flat_map(|y| (0..len_x).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let i = L::index(x, y, len_x, len_y);
+                assert_eq!(L::coords(i, len_x, len_y), (x, y));
+                i
+            })
+            .collect();
+        let mut sorted = indices.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..len_x * len_y).collect::<Vec<_>>());
+        indices
+    }
+
+    #[test]
+    fn teste_layouts() {
+        assert_eq!(check_layout::<RowMajor>(3, 2), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(check_layout::<ColMajor>(3, 2), vec![0, 2, 4, 1, 3, 5]);
+        // 2x2 tiles over a 3x3 grid:
+        // 0 1 | 4
+        // 2 3 | 5
+        // ----+--
+        // 6 7 | 8
+        assert_eq!(check_layout::<Tiled<2>>(3, 3), vec![0, 1, 4, 2, 3, 5, 6, 7, 8]);
+        for (len_x, len_y) in [(1, 1), (7, 5), (8, 8), (9, 17), (20, 3)] {
+            check_layout::<Tiled<4>>(len_x, len_y);
+            check_layout::<Tiled<1>>(len_x, len_y);
+        }
+    }
+
+    #[test]
+    fn teste_cells_mut() {
+        let mut flat = [0, 1, 2, 3, 4, 5, 6];
+        let mut cells = CellsMut::new(&mut flat, [1, 3, 4, 6].into_iter());
+        assert_eq!(cells.len(), 4);
+        *cells.next().unwrap() += 10;
+        *cells.next_back().unwrap() += 10;
+        *cells.next_back().unwrap() += 10;
+        *cells.next().unwrap() += 10;
+        assert!(cells.next().is_none() && cells.next_back().is_none());
+        assert_eq!(flat, [0, 11, 2, 13, 14, 5, 16]);
+    }
+}
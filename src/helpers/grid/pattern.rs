@@ -0,0 +1,155 @@
+//! Locating a smaller grid inside a larger one, optionally rotated or mirrored.
+//!
+//! Pattern cells are `Option`s, `None` is a wildcard matching any value. For example, finding a sea monster:
+//!
+//! ```
+//! use aoc2022::helpers::grid::{Grid, Orientation};
+//! let monster = Grid::parse(" # \n###", |c| Ok::<_, ()>((c == '#').then_some('#'))).unwrap();
+//! let sea = Grid::parse(".....\n..#..\n.###.\n.....", |c| Ok::<_, ()>(c)).unwrap();
+//! let matches = sea.find_pattern(&monster, &[Orientation::IDENTITY]);
+//! assert_eq!((matches[0].x, matches[0].y), (1, 1));
+//! ```
+
+use super::{Grid, Layout};
+
+/// One of the 8 ways of laying a grid down: mirrored with `flip_x` or not, then rotated clockwise by a number of
+/// quarter turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub flipped: bool,
+    pub quarter_turns: u8,
+}
+
+impl Orientation {
+    /// the grid as given.
+    pub const IDENTITY: Orientation = Orientation::new(false, 0);
+
+    /// all the 8 rotations and reflections, the 4 rotations of the original grid first.
+    pub const ALL: [Orientation; 8] = [
+        Orientation::new(false, 0),
+        Orientation::new(false, 1),
+        Orientation::new(false, 2),
+        Orientation::new(false, 3),
+        Orientation::new(true, 0),
+        Orientation::new(true, 1),
+        Orientation::new(true, 2),
+        Orientation::new(true, 3),
+    ];
+
+    pub const fn new(flipped: bool, quarter_turns: u8) -> Orientation {
+        Orientation {
+            flipped,
+            quarter_turns: quarter_turns % 4,
+        }
+    }
+
+    /// returns `grid` laid down in this orientation.
+    pub fn apply<T: Clone>(self, grid: &Grid<T>) -> Grid<T> {
+        let mut oriented = if self.flipped { grid.flip_x() } else { grid.clone() };
+        for _ in 0..self.quarter_turns {
+            oriented = oriented.rotate_cw();
+        }
+        oriented
+    }
+}
+
+/// An occurrence of a pattern, `(x, y)` is where the top left corner of the oriented pattern lies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PatternMatch {
+    pub x: usize,
+    pub y: usize,
+    pub orientation: Orientation,
+}
+
+impl<T: Clone + PartialEq, L: Layout> Grid<T, L> {
+    /// returns every occurrence of `pattern` in the grid, for each one of the `orientations` (`&Orientation::ALL` to
+    /// try every rotation and reflection). Occurrences may overlap. The matches are grouped by orientation, in the
+    /// given order, and in row major order inside each group. Symmetric patterns match the same cells under more than
+    /// one orientation, each one is reported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` has no cells.
+    pub fn find_pattern(&self, pattern: &Grid<Option<T>>, orientations: &[Orientation]) -> Vec<PatternMatch> {
+        assert!(pattern.size() > 0, "the pattern must not be empty");
+        let mut matches = Vec::new();
+        for &orientation in orientations {
+            let oriented = orientation.apply(pattern);
+            // only the fixed cells need to be compared
+            let fixed: Vec<(usize, usize, &T)> = oriented
+                .iter_indexed()
+                .filter_map(|(x, y, v)| v.as_ref().map(|v| (x, y, v)))
+                .collect();
+
+            let steps_x = (self.len_x + 1).saturating_sub(oriented.len_x);
+            let steps_y = (self.len_y + 1).saturating_sub(oriented.len_y);
+            for y in 0..steps_y {
+                for x in 0..steps_x {
+                    if fixed.iter().all(|&(px, py, v)| self.get(x + px, y + py) == v) {
+                        matches.push(PatternMatch { x, y, orientation });
+                    }
+                }
+            }
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(input: &str) -> Grid<Option<char>> {
+        Grid::parse(input, |c| Ok::<_, ()>((c != ' ').then_some(c))).unwrap()
+    }
+
+    #[test]
+    fn teste_orientation() {
+        let grid = Grid::from_vec(2, 1, vec![1, 2]);
+        assert_eq!(Orientation::IDENTITY.apply(&grid), grid);
+        assert_eq!(Orientation::new(false, 5).apply(&grid), grid.rotate_cw());
+        assert_eq!(Orientation::new(true, 2).apply(&grid), grid);
+        assert_eq!(Orientation::new(true, 1).apply(&grid), Grid::from_vec(1, 2, vec![2, 1]));
+    }
+
+    #[test]
+    fn teste_find_pattern() {
+        let sea = Grid::parse(
+            "#.....
+##....
+......
+...##.
+....#.",
+            Ok::<_, ()>,
+        )
+        .unwrap();
+        // an L shape, the wildcard corner makes it symmetric along a diagonal
+        let l = pattern("# \n##");
+
+        let found = sea.find_pattern(&l, &[Orientation::IDENTITY]);
+        assert_eq!(
+            found,
+            vec![PatternMatch {
+                x: 0,
+                y: 0,
+                orientation: Orientation::IDENTITY
+            }]
+        );
+
+        let found = sea.find_pattern(&l, &Orientation::ALL);
+        let found: Vec<(usize, usize, Orientation)> = found.iter().map(|m| (m.x, m.y, m.orientation)).collect();
+        assert_eq!(
+            found,
+            vec![
+                (0, 0, Orientation::new(false, 0)),
+                (3, 3, Orientation::new(false, 2)),
+                (0, 0, Orientation::new(true, 1)),
+                (3, 3, Orientation::new(true, 3)),
+            ]
+        );
+
+        assert!(sea.find_pattern(&pattern("#######"), &Orientation::ALL).is_empty());
+        // a lone wildcard matches everywhere
+        assert_eq!(sea.find_pattern(&pattern(" "), &[Orientation::IDENTITY]).len(), 30);
+    }
+}
@@ -0,0 +1,169 @@
+//! Flood fill and connected component labelling.
+
+use std::collections::VecDeque;
+
+use super::{Base2d, Grid, Layout};
+
+/// Which cells are considered adjacent when growing a region.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Connectivity {
+    /// above, below, to the left and to the right.
+    Four,
+    /// the 4 orthogonal neighbours and the 4 diagonal ones.
+    Eight,
+}
+
+/// Size and bounding box of a connected component, `min` and `max` are the inclusive corners of the box.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub size: usize,
+    pub min: Base2d<usize>,
+    pub max: Base2d<usize>,
+}
+
+impl<T, L: Layout> Grid<T, L> {
+    /// returns the coordinates of every cell reachable from `start` through orthogonal steps on cells for which
+    /// `predicate` is `true`, in the order they were reached. Returns an empty `Vec` if the `start` cell itself does
+    /// not match.
+    pub fn flood_fill<P>(&self, start: impl Into<(usize, usize)>, mut predicate: P) -> Vec<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut visited = Grid::new(self.len_x, self.len_y, false);
+        self.fill_from(start.into(), Connectivity::Four, &mut predicate, &mut visited)
+    }
+
+    /// labels the connected regions of cells for which `predicate` is `true`. Returns a grid with the id of the
+    /// component of each cell (`None` for cells not matching) and the size and bounding box of each component, indexed
+    /// by id. Ids are given in row major order of the first cell of each component.
+    pub fn label_components<P>(
+        &self,
+        mut predicate: P,
+        connectivity: Connectivity,
+    ) -> (Grid<Option<usize>>, Vec<Component>)
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut visited = Grid::new(self.len_x, self.len_y, false);
+        let mut labels = Grid::new(self.len_x, self.len_y, None);
+        let mut components = Vec::new();
+
+        for y in 0..self.len_y {
+            for x in 0..self.len_x {
+                if *visited.get(x, y) {
+                    continue;
+                }
+                let cells = self.fill_from((x, y), connectivity, &mut predicate, &mut visited);
+                if cells.is_empty() {
+                    continue;
+                }
+
+                let id = components.len();
+                let mut component = Component {
+                    size: cells.len(),
+                    min: Base2d::new(x, y),
+                    max: Base2d::new(x, y),
+                };
+                for (cx, cy) in cells {
+                    *labels.get_mut(cx, cy) = Some(id);
+                    component.min = Base2d::new(component.min.x.min(cx), component.min.y.min(cy));
+                    component.max = Base2d::new(component.max.x.max(cx), component.max.y.max(cy));
+                }
+                components.push(component);
+            }
+        }
+        (labels, components)
+    }
+
+    /// breadth first fill from `start`, marking every checked cell in `visited`.
+    fn fill_from<P>(
+        &self,
+        start: (usize, usize),
+        connectivity: Connectivity,
+        predicate: &mut P,
+        visited: &mut Grid<bool>,
+    ) -> Vec<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let mut cells = Vec::new();
+        *visited.get_mut(start.0, start.1) = true;
+        if !predicate(self.get(start.0, start.1)) {
+            return cells;
+        }
+
+        let mut queue = VecDeque::from([start]);
+        while let Some((x, y)) = queue.pop_front() {
+            cells.push((x, y));
+            let neighbors: Vec<_> = match connectivity {
+                Connectivity::Four => self.neighbors4_coords((x, y)).collect(),
+                Connectivity::Eight => self.neighbors8_coords((x, y)).collect(),
+            };
+            for (nx, ny) in neighbors {
+                let seen = visited.get_mut(nx, ny);
+                if !*seen && predicate(self.get(nx, ny)) {
+                    *seen = true;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(input: &str) -> Grid<char> {
+        Grid::parse(input, Ok::<_, ()>).unwrap()
+    }
+
+    #[test]
+    fn teste_flood_fill() {
+        let grid = map("..#.\n.#..\n#...");
+        let filled = grid.flood_fill((0, 0), |&c| c == '.');
+        assert_eq!(filled, vec![(0, 0), (1, 0), (0, 1)]);
+
+        let filled = grid.flood_fill(Base2d::new(3, 2), |&c| c == '.');
+        assert_eq!(filled.len(), 6);
+        assert!(grid.flood_fill((2, 0), |&c| c == '.').is_empty());
+    }
+
+    #[test]
+    fn teste_label_components() {
+        let grid = map("##..#\n#...#\n..#..\n.#..#");
+
+        let (labels, components) = grid.label_components(|&c| c == '#', Connectivity::Four);
+        assert_eq!(components.len(), 5);
+        assert_eq!(*labels.get(1, 0), Some(0));
+        assert_eq!(*labels.get(4, 1), Some(1));
+        assert_eq!(*labels.get(2, 2), Some(2));
+        assert_eq!(*labels.get(2, 0), None);
+        assert_eq!(
+            components[0],
+            Component {
+                size: 3,
+                min: Base2d::new(0, 0),
+                max: Base2d::new(1, 1)
+            }
+        );
+
+        let (_, components) = grid.label_components(|&c| c == '#', Connectivity::Eight);
+        assert_eq!(components.len(), 4);
+        assert_eq!(components[1].size, 2);
+        assert_eq!(components[2].min, Base2d::new(1, 2));
+        assert_eq!(components[2].max, Base2d::new(2, 3));
+    }
+
+    #[test]
+    fn teste_enclosed_regions() {
+        // open cells not touching the border are enclosed
+        let grid = map("#####\n#..##\n####.\n#.#..\n#####");
+        let (_, components) = grid.label_components(|&c| c == '.', Connectivity::Four);
+        let enclosed = components
+            .iter()
+            .filter(|c| c.min.x > 0 && c.min.y > 0 && c.max.x < grid.len_x - 1 && c.max.y < grid.len_y - 1);
+        assert_eq!(enclosed.count(), 2);
+    }
+}
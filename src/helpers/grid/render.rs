@@ -0,0 +1,106 @@
+//! Rendering of a grid as text or as a PGM/PPM image, handy for debugging.
+//!
+//! Images are written in the binary netpbm formats, which most image viewers open. For example, dumping the tree
+//! heights of day 08 as a grayscale image and the visibility mask as a colour one:
+//!
+//! ```no_run
+//! use aoc2022::helpers::grid::Grid;
+//! use std::fs::File;
+//!
+//! let heights = Grid::parse("30373\n25512\n65332", |c| c.to_digit(10).ok_or(c)).unwrap();
+//! heights.write_pgm(&mut File::create("heights.pgm").unwrap(), |&h| (h * 28) as u8).unwrap();
+//!
+//! let visible = Grid::new(5, 3, true);
+//! visible
+//!     .write_ppm(&mut File::create("visible.ppm").unwrap(), |&v| if v { [0, 200, 0] } else { [40, 40, 40] })
+//!     .unwrap();
+//! ```
+
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use super::{Grid, Layout};
+
+impl<T, L: Layout> Grid<T, L> {
+    /// renders the grid as text, one line per row, with each cell mapped to a char by `f`. There is no trailing new
+    /// line.
+    pub fn to_text<F>(&self, f: F) -> String
+    where
+        F: Fn(&T) -> char,
+    {
+        let mut s = String::with_capacity((self.len_x + 1) * self.len_y);
+        for y in 0..self.len_y {
+            if y > 0 {
+                s.push('\n');
+            }
+            s.extend(self.iter_row(y).map(&f));
+        }
+        s
+    }
+
+    /// writes the grid as a binary grayscale image (PGM), one pixel per cell, with each cell mapped to a gray level by
+    /// `f`.
+    pub fn write_pgm<W, F>(&self, w: &mut W, f: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> u8,
+    {
+        write!(w, "P5\n{} {}\n255\n", self.len_x, self.len_y)?;
+        let pixels: Vec<u8> = (0..self.len_y).flat_map(|y| self.iter_row(y)).map(f).collect();
+        w.write_all(&pixels)
+    }
+
+    /// writes the grid as a binary colour image (PPM), one pixel per cell, with each cell mapped to an `[r, g, b]`
+    /// colour by `f`.
+    pub fn write_ppm<W, F>(&self, w: &mut W, f: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> [u8; 3],
+    {
+        write!(w, "P6\n{} {}\n255\n", self.len_x, self.len_y)?;
+        let pixels: Vec<u8> = (0..self.len_y).flat_map(|y| self.iter_row(y)).flat_map(f).collect();
+        w.write_all(&pixels)
+    }
+}
+
+/// Writes the cells of each row one after the other, without separators, and the rows in separate lines.
+impl<T: fmt::Display, L: Layout> fmt::Display for Grid<T, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.len_y {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for v in self.iter_row(y) {
+                write!(f, "{}", v)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn teste_text() {
+        let grid = Grid::from_vec(3, 2, vec![1, 0, 0, 1, 1, 0]);
+        assert_eq!(grid.to_text(|&v| if v == 1 { '#' } else { '.' }), "#..\n##.");
+        assert_eq!(grid.to_string(), "100\n110");
+    }
+
+    #[test]
+    fn teste_images() {
+        let grid = Grid::from_vec(2, 1, vec![0u8, 9]);
+
+        let mut pgm = Vec::new();
+        grid.write_pgm(&mut pgm, |&v| v * 10).unwrap();
+        assert_eq!(pgm, b"P5\n2 1\n255\n\x00\x5a");
+
+        let mut ppm = Vec::new();
+        grid.write_ppm(&mut ppm, |&v| [v, 0, 255]).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\x00\x00\xff\x09\x00\xff");
+    }
+}
@@ -0,0 +1,300 @@
+//! Operations changing the dimensions of a grid: inserting and removing rows and columns, padding and cropping.
+//!
+//! A 0x0 grid takes the dimensions of the first row or column inserted into it. Removing the last row or column leaves
+//! a 0x0 grid. A grid 0 wide or 0 high but not both keeps its other dimension, so its rows or columns must be empty.
+
+use super::{Base2d, Grid};
+
+impl<T> Grid<T> {
+    //------------------------------
+    // Rows
+    //------------------------------
+
+    /// appends `row` after the last row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `row` is not `len_x`.
+    pub fn push_row(&mut self, row: Vec<T>) {
+        self.insert_row(self.len_y, row);
+    }
+
+    /// inserts `row` at position `y`, shifting the rows after it down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is greater than `len_y` or if the length of `row` is not `len_x`.
+    pub fn insert_row(&mut self, y: usize, row: Vec<T>) {
+        assert!(
+            y <= self.len_y,
+            "row {} is out of bounds, there are {} rows",
+            y,
+            self.len_y
+        );
+        if self.len_x == 0 && self.len_y == 0 {
+            self.len_x = row.len();
+        }
+        assert_eq!(row.len(), self.len_x, "the row length must be the grid width");
+
+        let i = self.len_x * y.min(self.len_y);
+        self.flat.splice(i..i, row);
+        self.len_y += 1;
+    }
+
+    /// removes and returns the row at position `y`, shifting the rows after it up. Removing the last row also sets
+    /// `len_x` to 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is out of bounds.
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        assert!(
+            y < self.len_y,
+            "row {} is out of bounds, there are {} rows",
+            y,
+            self.len_y
+        );
+        let i = self.len_x * y;
+        let row = self.flat.drain(i..i + self.len_x).collect();
+        self.len_y -= 1;
+        if self.len_y == 0 {
+            self.len_x = 0;
+        }
+        row
+    }
+
+    //------------------------------
+    // Columns
+    //------------------------------
+
+    /// appends `col` after the last column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `col` is not `len_y`.
+    pub fn push_col(&mut self, col: Vec<T>) {
+        self.insert_col(self.len_x, col);
+    }
+
+    /// inserts `col` at position `x`, shifting the columns after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is greater than `len_x` or if the length of `col` is not `len_y`.
+    pub fn insert_col(&mut self, x: usize, col: Vec<T>) {
+        assert!(
+            x <= self.len_x,
+            "column {} is out of bounds, there are {} columns",
+            x,
+            self.len_x
+        );
+        if self.len_x == 0 && self.len_y == 0 {
+            self.len_y = col.len();
+        }
+        assert_eq!(col.len(), self.len_y, "the column length must be the grid height");
+
+        let x = x.min(self.len_x);
+        let mut old = std::mem::take(&mut self.flat).into_iter();
+        let mut flat = Vec::with_capacity((self.len_x + 1) * self.len_y);
+        for value in col {
+            flat.extend(old.by_ref().take(x));
+            flat.push(value);
+            flat.extend(old.by_ref().take(self.len_x - x));
+        }
+        self.flat = flat;
+        self.len_x += 1;
+    }
+
+    /// removes and returns the column at position `x`, shifting the columns after it to the left. Removing the last
+    /// column also sets `len_y` to 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is out of bounds.
+    pub fn remove_col(&mut self, x: usize) -> Vec<T> {
+        assert!(
+            x < self.len_x,
+            "column {} is out of bounds, there are {} columns",
+            x,
+            self.len_x
+        );
+        let len_x = self.len_x;
+        let mut col = Vec::with_capacity(self.len_y);
+        let mut i = 0;
+        let old = std::mem::take(&mut self.flat);
+        self.flat = old
+            .into_iter()
+            .filter_map(|v| {
+                let keep = i % len_x != x;
+                i += 1;
+                if keep {
+                    Some(v)
+                } else {
+                    col.push(v);
+                    None
+                }
+            })
+            .collect();
+        self.len_x -= 1;
+        if self.len_x == 0 {
+            self.len_y = 0;
+        }
+        col
+    }
+
+    //------------------------------
+    // Cropping
+    //------------------------------
+
+    /// keeps only the rectangle with corners `min` and `max`, both inclusive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle is empty or does not fit inside the grid.
+    pub fn crop(&mut self, min: Base2d<usize>, max: Base2d<usize>) {
+        assert!(
+            min.x <= max.x && min.y <= max.y && max.x < self.len_x && max.y < self.len_y,
+            "cannot crop {:?}..={:?} from a {}x{} grid",
+            min,
+            max,
+            self.len_x,
+            self.len_y
+        );
+        let len_x = self.len_x;
+        let old = std::mem::take(&mut self.flat);
+        self.flat = old
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| (min.x..=max.x).contains(&(i % len_x)) && (min.y..=max.y).contains(&(i / len_x)))
+            .map(|(_, v)| v)
+            .collect();
+        self.len_x = max.x - min.x + 1;
+        self.len_y = max.y - min.y + 1;
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    //------------------------------
+    // Padding
+    //------------------------------
+
+    /// surrounds the grid with `n` cells of `fill` on every side.
+    pub fn pad(&mut self, n: usize, fill: T) {
+        self.pad_sides(n, n, n, n, fill);
+    }
+
+    /// adds `left` and `right` columns and `top` and `bottom` rows of `fill` around the grid. The cell that was at
+    /// `(x, y)` moves to `(x + left, y + top)`.
+    pub fn pad_sides(&mut self, left: usize, right: usize, top: usize, bottom: usize, fill: T) {
+        let len_x = left + self.len_x + right;
+        let len_y = top + self.len_y + bottom;
+        let mut old = std::mem::take(&mut self.flat).into_iter();
+
+        let mut flat = Vec::with_capacity(len_x * len_y);
+        flat.resize(len_x * top, fill.clone());
+        for _ in 0..self.len_y {
+            flat.extend(std::iter::repeat_n(fill.clone(), left));
+            flat.extend(old.by_ref().take(self.len_x));
+            flat.extend(std::iter::repeat_n(fill.clone(), right));
+        }
+        flat.resize(len_x * len_y, fill);
+
+        self.flat = flat;
+        self.len_x = len_x;
+        self.len_y = len_y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u32> {
+        Grid::from_vec(3, 2, vec![1, 2, 3, 4, 5, 6])
+    }
+
+    #[test]
+    fn teste_rows() {
+        let mut grid = grid();
+        grid.push_row(vec![7, 8, 9]);
+        grid.insert_row(0, vec![0, 0, 0]);
+        assert_eq!((grid.len_x(), grid.len_y()), (3, 4));
+        assert_eq!(grid.row(3), &[7, 8, 9]);
+        assert_eq!(grid.remove_row(1), vec![1, 2, 3]);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.len_y(), 3);
+
+        let mut empty = Grid::new(0, 0, 0);
+        empty.push_row(vec![1, 2]);
+        assert_eq!((empty.len_x(), empty.len_y()), (2, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn teste_row_length() {
+        grid().push_row(vec![1, 2]);
+    }
+
+    #[test]
+    fn teste_cols() {
+        let mut grid = grid();
+        grid.insert_col(1, vec![10, 20]);
+        grid.push_col(vec![30, 40]);
+        assert_eq!(grid.row(0), &[1, 10, 2, 3, 30]);
+        assert_eq!(grid.row(1), &[4, 20, 5, 6, 40]);
+        assert_eq!(grid.remove_col(0), vec![1, 4]);
+        assert_eq!(grid.row(1), &[20, 5, 6, 40]);
+        assert_eq!(grid.len_x(), 4);
+
+        let mut empty = Grid::new(0, 0, 0);
+        empty.push_col(vec![1, 2, 3]);
+        assert_eq!((empty.len_x(), empty.len_y()), (1, 3));
+        assert_eq!(empty.iter_col(0).copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn teste_remove_all() {
+        let mut grid = grid();
+        for _ in 0..3 {
+            grid.remove_col(0);
+        }
+        assert_eq!((grid.len_x(), grid.len_y()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid, Grid::new(0, 0, 0));
+
+        // a grid 0 wide but not 0 high keeps its height
+        let mut narrow = Grid::new(0, 3, 0u32);
+        assert_eq!(narrow.row(1), &[] as &[u32]);
+        assert_eq!(narrow.rows().count(), 0);
+        narrow.push_row(vec![]);
+        assert_eq!((narrow.len_x(), narrow.len_y()), (0, 4));
+        let mut flat = Grid::new(3, 0, 0u32);
+        flat.push_col(vec![]);
+        assert_eq!((flat.len_x(), flat.len_y()), (4, 0));
+
+        let mut grid = self::grid();
+        grid.remove_row(1);
+        grid.remove_row(0);
+        assert_eq!((grid.len_x(), grid.len_y()), (0, 0));
+        grid.push_col(vec![7]);
+        assert_eq!(grid.row(0), &[7]);
+    }
+
+    #[test]
+    fn teste_pad_and_crop() {
+        let mut grid = grid();
+        grid.pad_sides(1, 0, 2, 1, 0);
+        assert_eq!((grid.len_x(), grid.len_y()), (4, 5));
+        assert_eq!(grid[(1, 2)], 1);
+        assert_eq!(grid.row(3), &[0, 4, 5, 6]);
+        assert_eq!(grid.row(4), &[0, 0, 0, 0]);
+
+        grid.crop(Base2d::new(1, 2), Base2d::new(3, 3));
+        assert_eq!(grid, self::grid());
+
+        grid.pad(1, 9);
+        assert_eq!((grid.len_x(), grid.len_y()), (5, 4));
+        assert_eq!(grid.row(0), &[9; 5]);
+        assert_eq!(grid.row(2), &[9, 4, 5, 6, 9]);
+    }
+}
@@ -0,0 +1,231 @@
+//! A wrapper over `Grid` with a toroidal topology: walking past an edge comes back from the opposite one.
+//!
+//! Coordinates are signed and may be arbitrarily far from the grid, they are reduced modulo `len_x` and `len_y`. The
+//! wrapped grid is left untouched, so its bounded accessors are still available through `inner`.
+//!
+//! ```
+//! use aoc2022::helpers::{base2d::Base2d, grid::{Grid, Torus}};
+//! let mut torus = Torus::new(Grid::from_vec(3, 2, vec![0, 1, 2, 3, 4, 5]));
+//! assert_eq!(torus[Base2d::new(-1, 0)], 2);
+//! assert_eq!(torus[(3_000_000_001, -7)], 4);
+//! torus[(5, 5)] = 10;
+//! assert_eq!(torus.inner()[(2, 1)], 10);
+//! ```
+
+use std::ops::{Index, IndexMut};
+
+use super::{count_seen, Base2d, Direction, Grid, Layout, RowMajor, NEIGHBORS4, NEIGHBORS8};
+
+type Point = Base2d<i64>;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Torus<T, L: Layout = RowMajor> {
+    grid: Grid<T, L>,
+}
+
+impl<T, L: Layout> Torus<T, L> {
+    /// wraps `grid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the grid is empty, there would be nothing to wrap around.
+    pub fn new(grid: Grid<T, L>) -> Torus<T, L> {
+        assert!(grid.size() > 0, "cannot wrap an empty grid");
+        Torus { grid }
+    }
+
+    pub fn inner(&self) -> &Grid<T, L> {
+        &self.grid
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Grid<T, L> {
+        &mut self.grid
+    }
+
+    pub fn into_inner(self) -> Grid<T, L> {
+        self.grid
+    }
+
+    /// returns the coordinates inside the grid that `pos` wraps to.
+    pub fn wrap(&self, pos: impl Into<Point>) -> (usize, usize) {
+        let pos = pos.into();
+        (
+            pos.x.rem_euclid(self.grid.len_x as i64) as usize,
+            pos.y.rem_euclid(self.grid.len_y as i64) as usize,
+        )
+    }
+
+    //------------------------------
+    // Getters for single elements
+    //------------------------------
+
+    /// returns the value at `pos` after wrapping it, never panics.
+    pub fn get(&self, pos: impl Into<Point>) -> &T {
+        let (x, y) = self.wrap(pos);
+        self.grid.get(x, y)
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Point>) -> &mut T {
+        let (x, y) = self.wrap(pos);
+        self.grid.get_mut(x, y)
+    }
+
+    //------------------------------
+    // Neighbours
+    //------------------------------
+    //
+    // There are always 4 or 8 neighbours, given in row major order relative to `pos`, with wrapped coordinates. On
+    // grids narrower or shorter than 3 cells, the same cell may show up more than once, or be a neighbour of itself.
+
+    pub fn neighbors4_coords(&self, pos: impl Into<Point>) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbor_coords(pos.into(), &NEIGHBORS4)
+    }
+
+    pub fn neighbors8_coords(&self, pos: impl Into<Point>) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbor_coords(pos.into(), &NEIGHBORS8)
+    }
+
+    pub fn neighbors4(&self, pos: impl Into<Point>) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.neighbors4_coords(pos)
+            .map(move |(x, y)| (x, y, self.grid.get(x, y)))
+    }
+
+    pub fn neighbors8(&self, pos: impl Into<Point>) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.neighbors8_coords(pos)
+            .map(move |(x, y)| (x, y, self.grid.get(x, y)))
+    }
+
+    //------------------------------
+    // Rays
+    //------------------------------
+
+    /// returns an iterator over the wrapped coordinates of the cells walking from `pos` towards `direction`. The walk
+    /// stops right before coming back to `pos`, which is not included.
+    pub fn ray_coords(&self, pos: impl Into<Point>, direction: Direction) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let start = self.wrap(pos);
+        let (dx, dy) = direction.offset();
+        let mut curr = start;
+        std::iter::from_fn(move || {
+            curr = self.wrap(Point::new(curr.0 as i64 + dx as i64, curr.1 as i64 + dy as i64));
+            (curr != start).then_some(curr)
+        })
+        .fuse()
+    }
+
+    /// same as `ray_coords`, yielding `(x, y, &value)`.
+    pub fn ray(&self, pos: impl Into<Point>, direction: Direction) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.ray_coords(pos, direction)
+            .map(move |(x, y)| (x, y, self.grid.get(x, y)))
+    }
+
+    /// see `Grid::look`, the walk goes around the grid once at most.
+    pub fn look<F>(&self, pos: impl Into<Point>, direction: Direction, is_blocking: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        count_seen(self.ray(pos, direction).map(|(_, _, v)| v), is_blocking)
+    }
+
+    //------------------------------
+    // Private
+    //------------------------------
+
+    fn neighbor_coords(
+        &self,
+        pos: Point,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        // wrapping first keeps the sums far from overflowing
+        let (x, y) = self.wrap(pos);
+        offsets
+            .iter()
+            .map(move |&(dx, dy)| self.wrap(Point::new(x as i64 + dx as i64, y as i64 + dy as i64)))
+    }
+}
+
+//--------------------------------------------------------------------
+// Indexing
+//--------------------------------------------------------------------
+
+impl<T, L: Layout> Index<Point> for Torus<T, L> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &Self::Output {
+        self.get(pos)
+    }
+}
+
+impl<T, L: Layout> IndexMut<Point> for Torus<T, L> {
+    fn index_mut(&mut self, pos: Point) -> &mut Self::Output {
+        self.get_mut(pos)
+    }
+}
+
+impl<T, L: Layout> Index<(i64, i64)> for Torus<T, L> {
+    type Output = T;
+
+    fn index(&self, pos: (i64, i64)) -> &Self::Output {
+        self.get(Point::from_tuple(pos))
+    }
+}
+
+impl<T, L: Layout> IndexMut<(i64, i64)> for Torus<T, L> {
+    fn index_mut(&mut self, pos: (i64, i64)) -> &mut Self::Output {
+        self.get_mut(Point::from_tuple(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::grid::ColMajor;
+
+    fn torus() -> Torus<u32> {
+        Torus::new(Grid::from_vec(4, 3, (0..12).collect()))
+    }
+
+    #[test]
+    fn teste_wrap() {
+        let torus = torus();
+        assert_eq!(torus.wrap(Point::new(-1, -1)), (3, 2));
+        assert_eq!(torus.wrap(Point::new(4, 3)), (0, 0));
+        assert_eq!(torus.wrap(Point::new(i64::MIN, i64::MAX)), (0, 1));
+        assert_eq!(torus[(-5, 7)], 7);
+
+        let by_cols = Torus::new(torus.into_inner().to_layout::<ColMajor>());
+        assert_eq!(by_cols[(-5, 7)], 7);
+    }
+
+    #[test]
+    fn teste_neighbors() {
+        let torus = torus();
+        let corner: Vec<u32> = torus.neighbors4(Point::new(0, 0)).map(|(_, _, &v)| v).collect();
+        assert_eq!(corner, vec![8, 3, 1, 4]);
+        let far: Vec<_> = torus.neighbors4_coords(Point::new(i64::MAX, i64::MAX)).collect();
+        assert_eq!(far, vec![(3, 0), (2, 1), (0, 1), (3, 2)]);
+        let corner: Vec<_> = torus.neighbors8_coords(Point::new(3, 2)).collect();
+        assert_eq!(
+            corner,
+            vec![(2, 1), (3, 1), (0, 1), (2, 2), (0, 2), (2, 0), (3, 0), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn teste_ray() {
+        let torus = torus();
+        let east: Vec<u32> = torus
+            .ray(Point::new(2, 1), Direction::East)
+            .map(|(_, _, &v)| v)
+            .collect();
+        assert_eq!(east, vec![7, 4, 5]);
+        assert_eq!(torus.ray_coords(Point::new(0, 0), Direction::South).count(), 2);
+        // a diagonal goes through lcm(4, 3) cells before coming back
+        assert_eq!(torus.ray_coords(Point::new(0, 0), Direction::SouthEast).count(), 11);
+        assert_eq!(torus.look(Point::new(0, 0), Direction::West, |&v| v == 2), 2);
+        assert_eq!(torus.look(Point::new(0, 0), Direction::West, |&v| v > 100), 3);
+        // stays done once the walk is back to the start
+        let line = Torus::new(Grid::from_vec(2, 1, vec![0, 1]));
+        let mut ray = line.ray_coords(Point::new(0, 0), Direction::East);
+        assert_eq!((ray.next(), ray.next(), ray.next()), (Some((1, 0)), None, None));
+    }
+}
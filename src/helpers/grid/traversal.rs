@@ -0,0 +1,138 @@
+//! Scanning the grid in orders other than rows and columns: diagonals, anti-diagonals, spiral and snake.
+//!
+//! Every iterator yields the coordinates of each cell along with a reference to its value.
+
+use super::{Grid, Layout};
+
+type Cell<'a, T> = (usize, usize, &'a T);
+
+impl<T, L: Layout> Grid<T, L> {
+    /// returns an iterator over the diagonals running from top left to bottom right. The first diagonal holds only
+    /// the bottom left cell and the last one only the top right cell. Each diagonal starts from its top left end.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = Cell<'_, T>>> {
+        let (len_x, len_y) = (self.len_x, self.len_y);
+        (0..diagonal_count(len_x, len_y)).map(move |k| {
+            // the diagonal `k` holds the cells where x - y == k - (len_y - 1)
+            let (x0, y0) = if k < len_y {
+                (0, len_y - 1 - k)
+            } else {
+                (k + 1 - len_y, 0)
+            };
+            let len = (len_x - x0).min(len_y - y0);
+            (0..len).map(move |i| self.cell(x0 + i, y0 + i))
+        })
+    }
+
+    /// returns an iterator over the anti-diagonals running from top right to bottom left. The first anti-diagonal
+    /// holds only the top left cell and the last one only the bottom right cell. Each anti-diagonal starts from its
+    /// top right end.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = Cell<'_, T>>> {
+        let (len_x, len_y) = (self.len_x, self.len_y);
+        (0..diagonal_count(len_x, len_y)).map(move |k| {
+            // the anti-diagonal `k` holds the cells where x + y == k
+            let (x0, y0) = if k < len_x { (k, 0) } else { (len_x - 1, k + 1 - len_x) };
+            let len = (x0 + 1).min(len_y - y0);
+            (0..len).map(move |i| self.cell(x0 - i, y0 + i))
+        })
+    }
+
+    /// returns an iterator over every cell in clockwise spiral order, starting at the top left corner and moving
+    /// right along the outer ring, then inwards ring by ring.
+    pub fn spiral(&self) -> impl Iterator<Item = Cell<'_, T>> {
+        let rings = self.len_x.min(self.len_y).div_ceil(2);
+        (0..rings)
+            .flat_map(move |r| ring_coords(r, r, self.len_x - 1 - r, self.len_y - 1 - r))
+            .map(move |(x, y)| self.cell(x, y))
+    }
+
+    /// returns an iterator over every cell in snake order: the even rows from left to right and the odd rows from
+    /// right to left.
+    pub fn snake(&self) -> impl Iterator<Item = Cell<'_, T>> {
+        let len_x = self.len_x;
+        (0..self.len_y)
+            .flat_map(move |y| (0..len_x).map(move |i| if y % 2 == 0 { (i, y) } else { (len_x - 1 - i, y) }))
+            .map(move |(x, y)| self.cell(x, y))
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Cell<'_, T> {
+        (x, y, self.get(x, y))
+    }
+}
+
+/// returns the number of diagonals of a grid, which is the same for both directions.
+fn diagonal_count(len_x: usize, len_y: usize) -> usize {
+    if len_x == 0 || len_y == 0 {
+        0
+    } else {
+        len_x + len_y - 1
+    }
+}
+
+/// returns the coordinates along the border of the rectangle between `(left, top)` and `(right, bottom)`, clockwise
+/// from the top left corner.
+fn ring_coords(left: usize, top: usize, right: usize, bottom: usize) -> Vec<(usize, usize)> {
+    let mut coords: Vec<(usize, usize)> = (left..=right).map(|x| (x, top)).collect();
+    coords.extend((top + 1..=bottom).map(|y| (right, y)));
+    if top < bottom {
+        coords.extend((left..right).rev().map(|x| (x, bottom)));
+    }
+    if left < right {
+        coords.extend((top + 1..bottom).rev().map(|y| (left, y)));
+    }
+    coords
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<'a>(cells: impl Iterator<Item = Cell<'a, u32>>) -> Vec<u32> {
+        cells.map(|(_, _, &v)| v).collect()
+    }
+
+    #[test]
+    fn teste_diagonals() {
+        // 1 2 3 4
+        // 5 6 7 8
+        let grid = Grid::from_vec(4, 2, (1..=8).collect());
+        let diagonals: Vec<Vec<u32>> = grid.diagonals().map(values).collect();
+        assert_eq!(diagonals, vec![vec![5], vec![1, 6], vec![2, 7], vec![3, 8], vec![4]]);
+        let anti: Vec<Vec<u32>> = grid.anti_diagonals().map(values).collect();
+        assert_eq!(anti, vec![vec![1], vec![2, 5], vec![3, 6], vec![4, 7], vec![8]]);
+
+        let tall = Grid::from_vec(2, 3, (1..=6).collect());
+        let diagonals: Vec<Vec<u32>> = tall.diagonals().map(values).collect();
+        assert_eq!(diagonals, vec![vec![5], vec![3, 6], vec![1, 4], vec![2]]);
+        let last = tall.anti_diagonals().last().unwrap().collect::<Vec<_>>();
+        assert_eq!(last, vec![(1, 2, &6)]);
+
+        assert_eq!(Grid::<u32>::new(0, 0, 0).diagonals().count(), 0);
+    }
+
+    #[test]
+    fn teste_spiral() {
+        // 1  2  3  4
+        // 5  6  7  8
+        // 9 10 11 12
+        let grid = Grid::from_vec(4, 3, (1..=12).collect());
+        assert_eq!(values(grid.spiral()), vec![1, 2, 3, 4, 8, 12, 11, 10, 9, 5, 6, 7]);
+        assert_eq!(
+            values(grid.transpose().spiral()),
+            vec![1, 5, 9, 10, 11, 12, 8, 4, 3, 2, 6, 7]
+        );
+
+        let single_col = Grid::from_vec(1, 3, vec![1, 2, 3]);
+        assert_eq!(values(single_col.spiral()), vec![1, 2, 3]);
+        assert_eq!(
+            Grid::from_vec(3, 3, (1..=9).collect()).spiral().last(),
+            Some((1, 1, &5))
+        );
+    }
+
+    #[test]
+    fn teste_snake() {
+        let grid = Grid::from_vec(3, 3, (1..=9).collect());
+        assert_eq!(values(grid.snake()), vec![1, 2, 3, 6, 5, 4, 7, 8, 9]);
+        assert_eq!(grid.snake().nth(3), Some((2, 1, &6)));
+    }
+}
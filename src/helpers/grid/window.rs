@@ -0,0 +1,139 @@
+//! Sliding windows, convolution and summed-area tables.
+
+use std::{
+    marker::PhantomData,
+    ops::{Add, Sub},
+};
+
+use super::{Grid, GridView};
+
+impl<T> Grid<T> {
+    /// returns an iterator over every `w` by `h` region of the grid as a borrowed view, moving the window one cell at a
+    /// time in row major order. `GridView::offset` tells where each window is. Yields nothing if the window is larger
+    /// than the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `w` or `h` is zero.
+    pub fn windows(&self, w: usize, h: usize) -> impl Iterator<Item = GridView<'_, T>> {
+        assert!(w > 0 && h > 0, "window dimensions must be non-zero");
+        let steps_x = (self.len_x + 1).saturating_sub(w);
+        let steps_y = (self.len_y + 1).saturating_sub(h);
+        (0..steps_y)
+            .flat_map(move |y| (0..steps_x).map(move |x| (x, y)))
+            .map(move |(x, y)| self.view(x, y, w, h))
+    }
+
+    /// applies `kernel` to every `w` by `h` window, see `windows`. Returns a grid with the results, the value computed
+    /// for the window with the top left corner at `(x, y)` goes to `(x, y)`, so the result is `w - 1` columns narrower
+    /// and `h - 1` rows shorter than the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `w` or `h` is zero.
+    pub fn convolve<U, F>(&self, w: usize, h: usize, kernel: F) -> Grid<U>
+    where
+        F: FnMut(GridView<'_, T>) -> U,
+    {
+        let flat = self.windows(w, h).map(kernel).collect();
+        Grid {
+            flat,
+            len_x: (self.len_x + 1).saturating_sub(w),
+            len_y: (self.len_y + 1).saturating_sub(h),
+            layout: PhantomData,
+        }
+    }
+}
+
+/// A table of prefix sums over a grid, answering the sum of any rectangle in constant time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummedAreaTable<S> {
+    // one extra row and column of zeros, `table[(x, y)]` is the sum of the cells before column `x` and row `y`
+    table: Grid<S>,
+}
+
+impl<S> SummedAreaTable<S>
+where
+    S: Copy + Default + Add<Output = S> + Sub<Output = S>,
+{
+    /// builds the table from the values `value` maps each cell of `grid` to.
+    pub fn new<T, F>(grid: &Grid<T>, value: F) -> SummedAreaTable<S>
+    where
+        F: Fn(&T) -> S,
+    {
+        let mut table = Grid::new(grid.len_x + 1, grid.len_y + 1, S::default());
+        for y in 0..grid.len_y {
+            let mut row_sum = S::default();
+            for x in 0..grid.len_x {
+                row_sum = row_sum + value(grid.get(x, y));
+                *table.get_mut(x + 1, y + 1) = *table.get(x + 1, y) + row_sum;
+            }
+        }
+        SummedAreaTable { table }
+    }
+
+    /// returns the sum of the `len_x` by `len_y` rectangle with the top left corner at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle does not fit inside the grid.
+    pub fn sum(&self, x: usize, y: usize, len_x: usize, len_y: usize) -> S {
+        let (x1, y1) = (x + len_x, y + len_y);
+        assert!(
+            x1 < self.table.len_x && y1 < self.table.len_y,
+            "rectangle {}x{} at ({}, {}) does not fit in the grid",
+            len_x,
+            len_y,
+            x,
+            y
+        );
+        *self.table.get(x1, y1) + *self.table.get(x, y) - *self.table.get(x, y1) - *self.table.get(x1, y)
+    }
+
+    /// returns the sum of the whole grid.
+    pub fn total(&self) -> S {
+        *self.table.get(self.table.len_x - 1, self.table.len_y - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::rng::Rng;
+
+    #[test]
+    fn teste_windows() {
+        let grid = Grid::from_vec(4, 3, (0..12).collect::<Vec<u32>>());
+        let windows: Vec<_> = grid.windows(3, 2).collect();
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[3].offset(), (1, 1));
+        assert_eq!(windows[3].row(1), &[9, 10, 11]);
+        assert_eq!(grid.windows(5, 1).count(), 0);
+        assert_eq!(grid.windows(1, 1).count(), 12);
+    }
+
+    #[test]
+    fn teste_convolve() {
+        let grid = Grid::from_vec(4, 3, (0..12).collect::<Vec<u32>>());
+        let sums = grid.convolve(2, 2, |w| w.iter().sum::<u32>());
+        assert_eq!(sums, Grid::from_vec(3, 2, vec![10, 14, 18, 26, 30, 34]));
+
+        // count of trees taller than the center one in each 3x3 patch of a forest
+        let forest = Grid::parse("30373\n25512\n65332", |c| c.to_digit(10).ok_or(c)).unwrap();
+        let taller = forest.convolve(3, 3, |w| w.iter().filter(|&&h| h > *w.get(1, 1)).count());
+        assert_eq!(taller, Grid::from_vec(3, 1, vec![1, 1, 8]));
+    }
+
+    #[test]
+    fn teste_summed_area_table() {
+        let mut rng = Rng::new(43);
+        let grid = Grid::from_vec(7, 5, (0..35).map(|_| rng.range(0, 9) as i64).collect());
+        let table = SummedAreaTable::new(&grid, |&v| v);
+
+        assert_eq!(table.total(), grid.iter().sum::<i64>());
+        for (x, y, w, h) in [(0, 0, 7, 5), (2, 1, 3, 3), (6, 4, 1, 1), (3, 2, 0, 2)] {
+            let expected: i64 = grid.view(x, y, w, h).iter().sum();
+            assert_eq!(table.sum(x, y, w, h), expected);
+        }
+    }
+}
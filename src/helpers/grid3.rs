@@ -0,0 +1,327 @@
+//! A three-dimensional array using a flat internal representation, the 3d counterpart of `Grid`.
+//!
+//! Consecutive elements across the x dimension are next to each other, then come the rows (`y`) and then the layers
+//! (`z`), so the element at `(x, y, z)` lives at `(z * len_y + y) * len_x + x`.
+//!
+//! ```
+//! use aoc2022::helpers::{base3d::Base3d, grid3::{Axis, Grid3}};
+//! let mut grid = Grid3::new(3, 3, 3, false);
+//! grid[(1, 1, 1)] = true;
+//! assert_eq!(grid.neighbors6((1, 1, 0)).filter(|(_, _, _, &v)| v).count(), 1);
+//! assert!(grid.slice(Axis::Z, 1)[(1, 1)]);
+//! assert_eq!(grid.try_get(Base3d::new(-1, 0, 0)), None);
+//! ```
+
+use std::ops::{Index, IndexMut};
+
+use super::{
+    base3d::Base3d,
+    grid::{Grid, GridSizeError},
+};
+
+type Cell = (usize, usize, usize);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid3<T> {
+    flat: Vec<T>,
+    len_x: usize,
+    len_y: usize,
+    len_z: usize,
+}
+
+/// The axes of a `Grid3`, used for slicing it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl<T: Clone> Grid3<T> {
+    /// creates a new grid with all the elements having the `init`ial value
+    pub fn new(len_x: usize, len_y: usize, len_z: usize, init: T) -> Grid3<T> {
+        Grid3 {
+            flat: vec![init; len_x * len_y * len_z],
+            len_x,
+            len_y,
+            len_z,
+        }
+    }
+
+    /// returns the 2d layer at `index` along `axis`:
+    ///
+    /// - `Axis::Z`: the plane `z = index`, indexed by `(x, y)`;
+    /// - `Axis::Y`: the plane `y = index`, indexed by `(x, z)`;
+    /// - `Axis::X`: the plane `x = index`, indexed by `(y, z)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn slice(&self, axis: Axis, index: usize) -> Grid<T> {
+        let (len_a, len_b, len_axis) = match axis {
+            Axis::X => (self.len_y, self.len_z, self.len_x),
+            Axis::Y => (self.len_x, self.len_z, self.len_y),
+            Axis::Z => (self.len_x, self.len_y, self.len_z),
+        };
+        assert!(index < len_axis, "{:?} slice {} is out of bounds", axis, index);
+
+        let flat = (0..len_b)
+            .flat_map(|b| (0..len_a).map(move |a| (a, b)))
+            .map(|(a, b)| match axis {
+                Axis::X => self.get(index, a, b).clone(),
+                Axis::Y => self.get(a, index, b).clone(),
+                Axis::Z => self.get(a, b, index).clone(),
+            })
+            .collect();
+        Grid::from_vec(len_a, len_b, flat)
+    }
+}
+
+impl<T> Grid3<T> {
+    /// If vector `v` is larger than the grid size, the extra elements are truncated. Returns an error if it is smaller.
+    pub fn try_from_vec(len_x: usize, len_y: usize, len_z: usize, mut v: Vec<T>) -> Result<Grid3<T>, GridSizeError> {
+        let expected = len_x * len_y * len_z;
+        if v.len() < expected {
+            return Err(GridSizeError {
+                expected,
+                found: v.len(),
+            });
+        }
+        v.truncate(expected);
+
+        Ok(Grid3 {
+            flat: v,
+            len_x,
+            len_y,
+            len_z,
+        })
+    }
+
+    //------------------------------
+    // Getters for single elements
+    //------------------------------
+
+    /// returns the value at position x,y,z.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn get(&self, x: usize, y: usize, z: usize) -> &T {
+        &self.flat[self.index(x, y, z)]
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize, z: usize) -> &mut T {
+        let i = self.index(x, y, z);
+        &mut self.flat[i]
+    }
+
+    /// returns the value at position `pos`, or `None` if it is out of bounds.
+    pub fn try_get(&self, pos: Base3d<i64>) -> Option<&T> {
+        let (x, y, z) = self.checked_coords(pos)?;
+        Some(self.get(x, y, z))
+    }
+
+    pub fn try_get_mut(&mut self, pos: Base3d<i64>) -> Option<&mut T> {
+        let (x, y, z) = self.checked_coords(pos)?;
+        Some(self.get_mut(x, y, z))
+    }
+
+    //------------------------------
+    // Getters for multiple elements
+    //------------------------------
+
+    /// returns an iterator over the values, `x` varying faster and `z` slower.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.flat.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.flat.iter_mut()
+    }
+
+    //------------------------------
+    // Neighbours
+    //------------------------------
+    //
+    // Cells out of the grid are skipped. The position may be a tuple `(x, y, z)` or a `Base3d<usize>`.
+
+    /// returns an iterator over the coordinates of the 6 cells sharing a face with `pos`.
+    pub fn neighbors6_coords(&self, pos: impl Into<Cell>) -> impl Iterator<Item = Cell> {
+        self.neighbor_coords(pos.into(), false)
+    }
+
+    /// returns an iterator over the coordinates of the 26 cells surrounding `pos`.
+    pub fn neighbors26_coords(&self, pos: impl Into<Cell>) -> impl Iterator<Item = Cell> {
+        self.neighbor_coords(pos.into(), true)
+    }
+
+    /// returns an iterator over `(x, y, z, &value)` of the 6 cells sharing a face with `pos`.
+    pub fn neighbors6(&self, pos: impl Into<Cell>) -> impl Iterator<Item = (usize, usize, usize, &T)> {
+        self.neighbors6_coords(pos)
+            .map(move |(x, y, z)| (x, y, z, self.get(x, y, z)))
+    }
+
+    /// returns an iterator over `(x, y, z, &value)` of the 26 cells surrounding `pos`.
+    pub fn neighbors26(&self, pos: impl Into<Cell>) -> impl Iterator<Item = (usize, usize, usize, &T)> {
+        self.neighbors26_coords(pos)
+            .map(move |(x, y, z)| (x, y, z, self.get(x, y, z)))
+    }
+
+    //------------------------------
+    // Helpers
+    //------------------------------
+
+    /// number of elements across the x dimension.
+    pub fn len_x(&self) -> usize {
+        self.len_x
+    }
+
+    /// number of elements across the y dimension.
+    pub fn len_y(&self) -> usize {
+        self.len_y
+    }
+
+    /// number of elements across the z dimension.
+    pub fn len_z(&self) -> usize {
+        self.len_z
+    }
+
+    /// returns the total size of the array (len_x * len_y * len_z)
+    pub fn size(&self) -> usize {
+        self.flat.len()
+    }
+
+    //------------------------------
+    // Private
+    //------------------------------
+
+    fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        assert!(
+            x < self.len_x && y < self.len_y && z < self.len_z,
+            "position ({}, {}, {}) is out of bounds for a {}x{}x{} grid",
+            x,
+            y,
+            z,
+            self.len_x,
+            self.len_y,
+            self.len_z
+        );
+        (z * self.len_y + y) * self.len_x + x
+    }
+
+    fn checked_coords(&self, pos: Base3d<i64>) -> Option<Cell> {
+        let x = usize::try_from(pos.x).ok().filter(|&x| x < self.len_x)?;
+        let y = usize::try_from(pos.y).ok().filter(|&y| y < self.len_y)?;
+        let z = usize::try_from(pos.z).ok().filter(|&z| z < self.len_z)?;
+        Some((x, y, z))
+    }
+
+    /// returns the neighbours of `(x, y, z)` inside the grid, in the same order as the flat layout. If not `diagonals`,
+    /// only the ones sharing a face are returned.
+    fn neighbor_coords(&self, (x, y, z): Cell, diagonals: bool) -> impl Iterator<Item = Cell> {
+        let (len_x, len_y, len_z) = (self.len_x, self.len_y, self.len_z);
+        let offsets =
+            (-1isize..=1).flat_map(|dz| (-1isize..=1).flat_map(move |dy| (-1isize..=1).map(move |dx| (dx, dy, dz))));
+        offsets
+            .filter(move |&(dx, dy, dz)| {
+                let changed = (dx != 0) as u8 + (dy != 0) as u8 + (dz != 0) as u8;
+                changed > 0 && (diagonals || changed == 1)
+            })
+            .filter_map(move |(dx, dy, dz)| {
+                let nx = x.checked_add_signed(dx).filter(|&nx| nx < len_x)?;
+                let ny = y.checked_add_signed(dy).filter(|&ny| ny < len_y)?;
+                let nz = z.checked_add_signed(dz).filter(|&nz| nz < len_z)?;
+                Some((nx, ny, nz))
+            })
+    }
+}
+
+//--------------------------------------------------------------------
+// Indexing
+//--------------------------------------------------------------------
+
+impl<T> Index<Cell> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, (x, y, z): Cell) -> &Self::Output {
+        self.get(x, y, z)
+    }
+}
+
+impl<T> IndexMut<Cell> for Grid3<T> {
+    fn index_mut(&mut self, (x, y, z): Cell) -> &mut Self::Output {
+        self.get_mut(x, y, z)
+    }
+}
+
+impl<T> Index<Base3d<usize>> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, pos: Base3d<usize>) -> &Self::Output {
+        self.get(pos.x, pos.y, pos.z)
+    }
+}
+
+impl<T> IndexMut<Base3d<usize>> for Grid3<T> {
+    fn index_mut(&mut self, pos: Base3d<usize>) -> &mut Self::Output {
+        self.get_mut(pos.x, pos.y, pos.z)
+    }
+}
+
+//--------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::read;
+
+    #[test]
+    fn teste_layout_and_slices() {
+        let grid = Grid3::try_from_vec(2, 3, 2, (0..12).collect()).unwrap();
+        assert_eq!(*grid.get(1, 2, 1), 11);
+        assert_eq!(grid[Base3d::new(1, 0, 1)], 7);
+        assert_eq!(grid.try_get(Base3d::new(0, 3, 0)), None);
+        assert!(Grid3::try_from_vec(2, 2, 2, vec![0; 7]).is_err());
+
+        assert_eq!(grid.slice(Axis::Z, 1), Grid::from_vec(2, 3, vec![6, 7, 8, 9, 10, 11]));
+        assert_eq!(grid.slice(Axis::Y, 1), Grid::from_vec(2, 2, vec![2, 3, 8, 9]));
+        assert_eq!(grid.slice(Axis::X, 0), Grid::from_vec(3, 2, vec![0, 2, 4, 6, 8, 10]));
+    }
+
+    #[test]
+    #[should_panic(expected = "position (2, 0, 0) is out of bounds for a 2x3x2 grid")]
+    fn teste_index_out_of_bounds() {
+        // (2, 0, 0) would otherwise wrap to (0, 1, 0)
+        let _ = Grid3::new(2, 3, 2, 0).get(2, 0, 0);
+    }
+
+    #[test]
+    fn teste_neighbors() {
+        let grid = Grid3::new(3, 3, 3, 0);
+        assert_eq!(grid.neighbors6_coords((1, 1, 1)).count(), 6);
+        assert_eq!(grid.neighbors26_coords((1, 1, 1)).count(), 26);
+        assert_eq!(grid.neighbors6_coords(Base3d::new(0, 0, 0)).count(), 3);
+        assert_eq!(grid.neighbors26_coords((0, 0, 0)).count(), 7);
+        assert_eq!(
+            grid.neighbors6_coords((0, 1, 2)).collect::<Vec<_>>(),
+            vec![(0, 1, 1), (0, 0, 2), (1, 1, 2), (0, 2, 2)]
+        );
+    }
+
+    #[test]
+    fn teste_lava_droplet_surface() {
+        let cubes: Vec<Base3d<usize>> = read::lines_into_vec(
+            "2,2,2\n1,2,2\n3,2,2\n2,1,2\n2,3,2\n2,2,1\n2,2,3\n2,2,4\n2,2,6\n1,2,5\n3,2,5\n2,1,5\n2,3,5",
+        );
+        let mut grid = Grid3::new(4, 4, 7, false);
+        cubes.iter().for_each(|&c| grid[c] = true);
+
+        let covered: usize = cubes
+            .iter()
+            .map(|&c| grid.neighbors6(c).filter(|(_, _, _, &v)| v).count())
+            .sum();
+        assert_eq!(cubes.len() * 6 - covered, 64);
+    }
+}
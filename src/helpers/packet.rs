@@ -0,0 +1,209 @@
+//! A nested value made of integers and lists, as in `[1,[2,[3,4]],5]`.
+//!
+//! Packets are parsed from strings with `FromStr` and written back with `Display` in the same format, without any
+//! whitespace. They are ordered following the "distress signal" rules:
+//!
+//! - two integers are compared by value;
+//! - two lists are compared element by element, the first difference decides. If all elements are the same, the
+//!   shorter list comes first;
+//! - when comparing an integer with a list, the integer is promoted to a list with a single element.
+//!
+//! Equality follows the same rules, so `3` and `[3]` are equal packets.
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+//------------------------------
+// Parsing
+//------------------------------
+
+impl FromStr for Packet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s.trim().as_bytes();
+        let (packet, end) = parse_value(bytes, 0)?;
+        if end != bytes.len() {
+            return Err(format!("Unexpected character at position {}.", end));
+        }
+        Ok(packet)
+    }
+}
+
+/// parses a single value starting at `pos`, returning it along with the position right after it.
+fn parse_value(s: &[u8], pos: usize) -> Result<(Packet, usize), String> {
+    match s.get(pos) {
+        Some(b'[') => parse_list(s, pos + 1),
+        Some(c) if c.is_ascii_digit() => {
+            let end = (pos..s.len()).find(|&i| !s[i].is_ascii_digit()).unwrap_or(s.len());
+            let n = std::str::from_utf8(&s[pos..end])
+                .unwrap() // only ascii digits
+                .parse()
+                .map_err(|_| format!("Integer out of range at position {}.", pos))?;
+            Ok((Packet::Int(n), end))
+        }
+        Some(_) => Err(format!("Expected an integer or a list at position {}.", pos)),
+        None => Err("Unexpected end of input.".to_string()),
+    }
+}
+
+/// parses the elements of a list whose opening bracket is right before `pos`.
+fn parse_list(s: &[u8], mut pos: usize) -> Result<(Packet, usize), String> {
+    let mut list = Vec::new();
+    if s.get(pos) == Some(&b']') {
+        return Ok((Packet::List(list), pos + 1));
+    }
+
+    loop {
+        let (value, end) = parse_value(s, pos)?;
+        list.push(value);
+        match s.get(end) {
+            Some(b',') => pos = end + 1,
+            Some(b']') => return Ok((Packet::List(list), end + 1)),
+            Some(_) => return Err(format!("Expected ',' or ']' at position {}.", end)),
+            None => return Err("Unexpected end of input, missing ']'.".to_string()),
+        }
+    }
+}
+
+//------------------------------
+// Display
+//------------------------------
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Int(n) => write!(f, "{}", n),
+            Packet::List(list) => {
+                write!(f, "[")?;
+                for (i, p) in list.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", p)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+//------------------------------
+// Ordering
+//------------------------------
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(a), Packet::Int(b)) => a.cmp(b),
+            (Packet::List(a), Packet::List(b)) => a.cmp(b), // lexicographic, the shorter list first on ties
+            (Packet::Int(_), Packet::List(b)) => std::slice::from_ref(self).cmp(b.as_slice()),
+            (Packet::List(a), Packet::Int(_)) => a.as_slice().cmp(std::slice::from_ref(other)),
+        }
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+//------------------------------
+// Tests
+//------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::read;
+    use Packet::*;
+
+    const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+    #[test]
+    fn teste_parse() {
+        let p: Packet = "[1,[2,[3,4]],5]".parse().unwrap();
+        let expected = List(vec![Int(1), List(vec![Int(2), List(vec![Int(3), Int(4)])]), Int(5)]);
+        assert_eq!(p.to_string(), expected.to_string());
+        assert_eq!("[]".parse(), Ok(List(vec![])));
+        assert_eq!("10".parse(), Ok(Int(10)));
+
+        assert!("[1,2".parse::<Packet>().is_err());
+        assert!("[1,,2]".parse::<Packet>().is_err());
+        assert!("[1]]".parse::<Packet>().is_err());
+        assert!("[a]".parse::<Packet>().is_err());
+        assert!("".parse::<Packet>().is_err());
+    }
+
+    #[test]
+    fn teste_display_round_trip() {
+        for line in EXAMPLE.lines().filter(|l| !l.is_empty()) {
+            let p: Packet = line.parse().unwrap();
+            assert_eq!(p.to_string(), line);
+        }
+    }
+
+    #[test]
+    fn teste_ordering_pairs() {
+        let packets: Vec<Packet> = read::lines_into_vec(EXAMPLE);
+        let right_order: usize = packets
+            .chunks(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0] < pair[1])
+            .map(|(i, _)| i + 1)
+            .sum();
+        assert_eq!(right_order, 13);
+    }
+
+    #[test]
+    fn teste_ordering_sort() {
+        let dividers: [Packet; 2] = ["[[2]]".parse().unwrap(), "[[6]]".parse().unwrap()];
+        let mut packets: Vec<Packet> = read::lines_into_vec(EXAMPLE);
+        packets.extend(dividers.iter().cloned());
+        packets.sort();
+
+        let decoder_key: usize = dividers
+            .iter()
+            .map(|d| packets.iter().position(|p| p == d).unwrap() + 1)
+            .product();
+        assert_eq!(decoder_key, 140);
+        assert_eq!(Int(3), List(vec![Int(3)]));
+        assert_ne!(List(vec![]), List(vec![List(vec![])]));
+    }
+}
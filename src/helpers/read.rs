@@ -1,10 +1,12 @@
-//todo: implement tests
-
 use std::{fs::File, io, io::prelude::*, str::FromStr};
 
 //--------------------------------------------------------------------
 // Read File
 //--------------------------------------------------------------------
+//
+// The `file_*` functions are thin wrappers over the `reader_*` ones, opening a file inside the `inputs` folder and
+// handing a buffered reader over it. The `reader_*` versions accept any `BufRead`, so they also work with in-memory
+// cursors, stdin, sockets or decompressed streams.
 
 //------------------------------
 // Buffered Reader
@@ -22,29 +24,51 @@ pub fn to_bufreader(filename: &str) -> io::Result<io::BufReader<File>> {
 
 /// reads the whole file into a String.
 pub fn file_to_string(filename: &str) -> io::Result<String> {
-    let mut file = get_file(filename)?;
+    reader_to_string(to_bufreader(filename)?)
+}
+
+/// reads the whole content of a buffered reader into a String.
+pub fn reader_to_string<R: BufRead>(mut reader: R) -> io::Result<String> {
     let mut s = String::new();
-    file.read_to_string(&mut s)?;
+    reader.read_to_string(&mut s)?;
     Ok(s)
 }
 
-/// splits the contents of the file at `split_at` and parses each section into T, returning a Vec<T>
+/// splits the contents of the file at `split_at` and parses each section into T, returning a `Vec<T>`
 /// T may be a String or any type that can be parsed from a string
 pub fn file_to_vec<T>(filename: &str, split_bit: u8) -> io::Result<Vec<T>>
 where
     T: FromStr,
 {
-    let iter = file_to_string_iter(filename, split_bit)?;
-    Ok(iter.flat_map(|s| s.parse()).collect())
+    reader_to_vec(to_bufreader(filename)?, split_bit)
 }
 
-/// reads the file parsing each line into type T and returning a Vec<T>
+/// splits the contents of the reader at `split_bit` and parses each section into T, returning a `Vec<T>`
+pub fn reader_to_vec<T, R>(reader: R, split_bit: u8) -> io::Result<Vec<T>>
+where
+    T: FromStr,
+    R: BufRead,
+{
+    Ok(reader_to_string_iter(reader, split_bit)
+        .flat_map(|s| s.parse())
+        .collect())
+}
+
+/// reads the file parsing each line into type T and returning a `Vec<T>`
 pub fn file_lines_to_vec<T>(filename: &str) -> io::Result<Vec<T>>
 where
     T: FromStr,
 {
-    let iter = file_to_iter::<T>(filename)?;
-    Ok(iter.collect())
+    reader_lines_to_vec(to_bufreader(filename)?)
+}
+
+/// reads the reader parsing each line into type T and returning a `Vec<T>`
+pub fn reader_lines_to_vec<T, R>(reader: R) -> io::Result<Vec<T>>
+where
+    T: FromStr,
+    R: BufRead,
+{
+    Ok(reader_to_iter(reader).collect())
 }
 
 //------------------------------
@@ -53,8 +77,12 @@ where
 
 // returns an Iterator over lines of a file
 pub fn file_to_lines(filename: &str) -> io::Result<io::Lines<io::BufReader<File>>> {
-    let file = get_file(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    Ok(reader_to_lines(to_bufreader(filename)?))
+}
+
+// returns an Iterator over lines of a buffered reader
+pub fn reader_to_lines<R: BufRead>(reader: R) -> io::Lines<R> {
+    reader.lines()
 }
 
 // returns an Iterator over a buffered reader, lines are parsed into type T
@@ -62,18 +90,36 @@ pub fn file_to_iter<T>(filename: &str) -> io::Result<impl Iterator<Item = T>>
 where
     T: FromStr,
 {
-    let file = get_file(filename)?;
-    let iter = io::BufReader::new(file).lines().flatten().flat_map(|s| s.parse::<T>());
-    Ok(iter)
+    Ok(reader_to_iter(to_bufreader(filename)?))
+}
+
+// returns an Iterator over the lines of a buffered reader parsed into type T. Lines that are not valid utf-8 or fail to
+// be parsed are skipped, any other read error ends the iteration, as the reader may keep failing forever.
+pub fn reader_to_iter<T, R>(reader: R) -> impl Iterator<Item = T>
+where
+    T: FromStr,
+    R: BufRead,
+{
+    reader
+        .lines()
+        .filter(|line| !is_invalid_data(line))
+        .map_while(Result::ok)
+        .flat_map(|s| s.parse::<T>())
 }
 
 pub fn file_to_string_iter(filename: &str, split_bit: u8) -> io::Result<impl Iterator<Item = String>> {
-    let bf = to_bufreader(filename)?;
-    Ok(bf
+    Ok(reader_to_string_iter(to_bufreader(filename)?, split_bit))
+}
+
+// returns an Iterator over the non empty sections of a buffered reader split at `split_bit`. Sections that are not
+// valid utf-8 are skipped, any read error ends the iteration (see `reader_to_iter`).
+pub fn reader_to_string_iter<R: BufRead>(reader: R, split_bit: u8) -> impl Iterator<Item = String> {
+    reader
         .split(split_bit)
-        .flatten()
+        .filter(|section| !is_invalid_data(section))
+        .map_while(Result::ok)
         .filter(|v| !v.is_empty())
-        .flat_map(String::from_utf8))
+        .flat_map(String::from_utf8)
 }
 
 //--------------------------------------------------------------------
@@ -120,6 +166,48 @@ where
     input.split(split_at).flat_map(str::parse::<T>)
 }
 
+//------------------------------
+// Diagrams
+//------------------------------
+
+/// parses a column aligned ascii `diagram` ending with a footer row of labels, like:
+///
+/// ```text
+///     [D]
+/// [N] [C]
+/// [Z] [M] [P]
+///  1   2   3
+/// ```
+///
+/// The position of the last character of each label in the footer sets the position of a column, so numeric labels are
+/// expected to be right aligned with the cells. Returns a `Vec` per column with the non-blank cells found at that
+/// position, from the bottom to the top. Rows shorter than a label position are considered blank at that column.
+/// Returns an empty `Vec` if there is no footer.
+pub fn columns_from_diagram(diagram: &str) -> Vec<Vec<u8>> {
+    let mut rows = diagram.trim_end_matches(['\n', '\r']).lines().rev();
+    let footer = match rows.next() {
+        Some(footer) => footer.as_bytes(),
+        None => return Vec::new(),
+    };
+
+    // a label ends at every non-blank byte followed by a blank one
+    let positions: Vec<usize> = (0..footer.len())
+        .filter(|&i| !footer[i].is_ascii_whitespace() && footer.get(i + 1).is_none_or(|c| c.is_ascii_whitespace()))
+        .collect();
+
+    let mut columns = vec![Vec::new(); positions.len()];
+    for row in rows.map(str::as_bytes) {
+        for (column, &p) in columns.iter_mut().zip(&positions) {
+            match row.get(p) {
+                Some(&c) if !c.is_ascii_whitespace() => column.push(c),
+                _ => (),
+            }
+        }
+    }
+
+    columns
+}
+
 //--------------------------------------------------------------------
 // Helpers
 //--------------------------------------------------------------------
@@ -128,6 +216,11 @@ fn get_file(filename: &str) -> Result<File, io::Error> {
     File::open("inputs/".to_string() + filename)
 }
 
+/// tells if `result` failed on content that could not be decoded, which only spoils that line.
+fn is_invalid_data<T>(result: &io::Result<T>) -> bool {
+    matches!(result, Err(e) if e.kind() == io::ErrorKind::InvalidData)
+}
+
 //--------------------------------------------------------------------
 // Others
 //--------------------------------------------------------------------
@@ -143,3 +236,73 @@ where
 {
     input.lines().map(str::parse::<T>).flatten()
 }
+
+//--------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn teste_reader_to_string() {
+        let s = reader_to_string(Cursor::new("1\n2\n3")).unwrap();
+        assert_eq!(s, "1\n2\n3");
+    }
+
+    #[test]
+    fn teste_reader_to_vec() {
+        let v: Vec<u32> = reader_to_vec(Cursor::new("1,2,,x,3"), b',').unwrap();
+        assert_eq!(v, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn teste_reader_lines() {
+        let v: Vec<i32> = reader_lines_to_vec(Cursor::new("10\n-2\nabc\n7\n")).unwrap();
+        assert_eq!(v, vec![10, -2, 7]);
+
+        let lines: Vec<String> = reader_to_lines(Cursor::new("a b\nc")).map_while(Result::ok).collect();
+        assert_eq!(lines, vec!["a b", "c"]);
+    }
+
+    #[test]
+    fn teste_columns_from_diagram() {
+        let diagram = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n";
+        let columns = columns_from_diagram(diagram);
+        assert_eq!(columns, vec![b"ZN".to_vec(), b"MCD".to_vec(), b"P".to_vec()]);
+
+        // trailing blanks trimmed, more than 9 columns
+        let diagram = "                                        [A]\n 1   2   3   4   5   6   7   8   9  10  11";
+        let columns = columns_from_diagram(diagram);
+        assert_eq!(columns.len(), 11);
+        assert!(columns[9].is_empty());
+        assert_eq!(columns[10], b"A".to_vec());
+
+        assert!(columns_from_diagram("").is_empty());
+    }
+
+    #[test]
+    fn teste_reader_errors() {
+        // invalid utf-8 only spoils its own line
+        let v: Vec<u32> = reader_lines_to_vec(Cursor::new(b"1\n\xff\n3\n".as_slice())).unwrap();
+        assert_eq!(v, vec![1, 3]);
+
+        // any other error ends the iteration instead of spinning on a broken reader
+        struct Broken;
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::ConnectionReset, "gone"))
+            }
+        }
+        assert_eq!(reader_to_iter::<u32, _>(io::BufReader::new(Broken)).count(), 0);
+        assert_eq!(reader_to_string_iter(io::BufReader::new(Broken), b',').count(), 0);
+    }
+
+    #[test]
+    fn teste_reader_to_string_iter() {
+        let v: Vec<String> = reader_to_string_iter(Cursor::new(b"ab\n\ncd\n".as_slice()), b'\n').collect();
+        assert_eq!(v, vec!["ab", "cd"]);
+    }
+}
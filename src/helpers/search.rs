@@ -0,0 +1,372 @@
+//! Path finding: breadth first search, Dijkstra and A*.
+//!
+//! The free functions work over any node type, the neighbours of a node are given by a closure. The `Grid` methods
+//! are shortcuts for searching over the 4 neighbours of each cell, with closures deciding if a step between two cells
+//! is allowed and how much it costs.
+//!
+//! All searches accept multiple start nodes and stop as soon as a node matching the `is_goal` predicate is reached.
+//! Passing `|_| false` explores everything reachable, which is handy for building a full distance map.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use super::grid::{Grid, Layout};
+
+type Cell = (usize, usize);
+
+//--------------------------------------------------------------------
+// Result
+//--------------------------------------------------------------------
+
+/// The outcome of a search: the goal reached (if any), the distance to every settled node and how to walk back to
+/// the start.
+///
+/// A node is settled once its shortest distance is known. Breadth first search settles the nodes as soon as they are
+/// reached, Dijkstra and A* when they are taken out of the queue. When the search stops at the goal, the nodes still
+/// waiting in the queue are not settled and are left out. With an A* heuristic that is admissible but not consistent,
+/// a node taken out of the queue may still be improved later, so only the goal distance is sure to be the shortest.
+#[derive(Debug, Clone)]
+pub struct SearchResult<N> {
+    pub goal: Option<N>,
+    distances: HashMap<N, u32>,
+    previous: HashMap<N, N>,
+    /// dimensions of the grid searched, for the searches started from a `Grid`
+    grid_size: Option<(usize, usize)>,
+}
+
+impl<N: Copy + Eq + Hash> SearchResult<N> {
+    fn new() -> Self {
+        SearchResult {
+            goal: None,
+            distances: HashMap::new(),
+            previous: HashMap::new(),
+            grid_size: None,
+        }
+    }
+
+    /// returns the distance from the closest start to `node`, or `None` if it was not settled.
+    pub fn distance(&self, node: N) -> Option<u32> {
+        self.distances.get(&node).copied()
+    }
+
+    /// returns the distance to the goal, or `None` if no goal was reached.
+    pub fn goal_distance(&self) -> Option<u32> {
+        self.distance(self.goal?)
+    }
+
+    /// returns the nodes from a start to `node`, both included, or `None` if `node` was not settled.
+    pub fn path_to(&self, node: N) -> Option<Vec<N>> {
+        self.distances.get(&node)?;
+        let mut path = vec![node];
+        let mut curr = node;
+        while let Some(&prev) = self.previous.get(&curr) {
+            path.push(prev);
+            curr = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// returns the nodes from a start to the goal, both included, or `None` if no goal was reached.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal?)
+    }
+
+    /// returns an iterator over every settled node and its distance, in no particular order.
+    pub fn distances(&self) -> impl Iterator<Item = (N, u32)> + '_ {
+        self.distances.iter().map(|(&n, &d)| (n, d))
+    }
+}
+
+impl SearchResult<Cell> {
+    /// returns a grid with the same dimensions as the one searched, holding the distance to each cell, `None` if the
+    /// cell was not settled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the search was not started from a `Grid` method, the free functions do not know any dimensions.
+    pub fn to_grid(&self) -> Grid<Option<u32>> {
+        let (len_x, len_y) = self
+            .grid_size
+            .expect("only the searches over a Grid can be turned into a grid");
+        let mut grid = Grid::new(len_x, len_y, None);
+        for (&(x, y), &d) in &self.distances {
+            *grid.get_mut(x, y) = Some(d);
+        }
+        grid
+    }
+}
+
+//--------------------------------------------------------------------
+// Generic searches
+//--------------------------------------------------------------------
+
+/// breadth first search, every step costs 1.
+pub fn bfs<N, I, F, G>(starts: impl IntoIterator<Item = N>, mut neighbors: F, mut is_goal: G) -> SearchResult<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    F: FnMut(N) -> I,
+    G: FnMut(N) -> bool,
+{
+    let mut result = SearchResult::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(e) = result.distances.entry(start) {
+            e.insert(0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(node) {
+            result.goal = Some(node);
+            break;
+        }
+        let distance = result.distances[&node] + 1;
+        for next in neighbors(node) {
+            if let Entry::Vacant(e) = result.distances.entry(next) {
+                e.insert(distance);
+                result.previous.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+    result
+}
+
+/// Dijkstra's search, `neighbors` returns each neighbour along with the cost of stepping into it.
+pub fn dijkstra<N, I, F, G>(starts: impl IntoIterator<Item = N>, neighbors: F, is_goal: G) -> SearchResult<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, u32)>,
+    F: FnMut(N) -> I,
+    G: FnMut(N) -> bool,
+{
+    astar(starts, neighbors, |_| 0, is_goal)
+}
+
+/// A* search, `neighbors` returns each neighbour along with the cost of stepping into it. The `heuristic` estimates
+/// the cost from a node to the goal, it must never overestimate it (admissible), otherwise the path found may not be
+/// the shortest. A node is reopened when a shorter way to it shows up after it left the queue, which only happens if
+/// the heuristic is not consistent (it drops by more than the cost of a step).
+pub fn astar<N, I, F, H, G>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> SearchResult<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, u32)>,
+    F: FnMut(N) -> I,
+    H: FnMut(N) -> u32,
+    G: FnMut(N) -> bool,
+{
+    let mut result = SearchResult::new();
+    // best distance found so far and where it came from, copied into `result` when the node leaves the queue
+    let mut tentative: HashMap<N, (u32, Option<N>)> = HashMap::new();
+    // the heap holds indexes into `nodes`, so `N` does not need to be `Ord`
+    let mut nodes: Vec<N> = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if let Entry::Vacant(e) = tentative.entry(start) {
+            e.insert((0, None));
+            heap.push(Reverse((heuristic(start), 0, nodes.len())));
+            nodes.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, distance, id))) = heap.pop() {
+        let node = nodes[id];
+        if distance > tentative[&node].0 {
+            continue; // a shorter way to this node was already processed
+        }
+        result.distances.insert(node, distance);
+        if let Some(prev) = tentative[&node].1 {
+            result.previous.insert(node, prev);
+        }
+        if is_goal(node) {
+            result.goal = Some(node);
+            break;
+        }
+        for (next, cost) in neighbors(node) {
+            let next_distance = distance + cost;
+            if tentative.get(&next).is_none_or(|&(d, _)| next_distance < d) {
+                tentative.insert(next, (next_distance, Some(node)));
+                // reopened, it goes back into `result` when it leaves the queue again
+                result.distances.remove(&next);
+                result.previous.remove(&next);
+                heap.push(Reverse((next_distance + heuristic(next), next_distance, nodes.len())));
+                nodes.push(next);
+            }
+        }
+    }
+    result
+}
+
+//--------------------------------------------------------------------
+// Searches over a Grid
+//--------------------------------------------------------------------
+
+impl<T, L: Layout> Grid<T, L> {
+    /// breadth first search over the 4 neighbours of each cell. `can_move(from, to)` receives the values of both cells
+    /// and tells if the step is allowed.
+    pub fn bfs<P, G>(&self, starts: impl IntoIterator<Item = Cell>, mut can_move: P, is_goal: G) -> SearchResult<Cell>
+    where
+        P: FnMut(&T, &T) -> bool,
+        G: FnMut(Cell) -> bool,
+    {
+        let neighbors = |(x, y): Cell| {
+            let from = self.get(x, y);
+            self.neighbors4((x, y))
+                .filter(|&(_, _, to)| can_move(from, to))
+                .map(|(nx, ny, _)| (nx, ny))
+                .collect::<Vec<_>>()
+        };
+        let mut result = bfs(starts, neighbors, is_goal);
+        result.grid_size = Some((self.len_x(), self.len_y()));
+        result
+    }
+
+    /// Dijkstra's search over the 4 neighbours of each cell. `cost(from, to)` receives the values of both cells and
+    /// returns the cost of the step, or `None` if it is not allowed.
+    pub fn dijkstra<C, G>(&self, starts: impl IntoIterator<Item = Cell>, cost: C, is_goal: G) -> SearchResult<Cell>
+    where
+        C: FnMut(&T, &T) -> Option<u32>,
+        G: FnMut(Cell) -> bool,
+    {
+        self.astar(starts, cost, |_| 0, is_goal)
+    }
+
+    /// A* search over the 4 neighbours of each cell. `cost(from, to)` receives the values of both cells and returns
+    /// the cost of the step, or `None` if it is not allowed. See `astar` about the `heuristic`.
+    pub fn astar<C, H, G>(
+        &self,
+        starts: impl IntoIterator<Item = Cell>,
+        mut cost: C,
+        heuristic: H,
+        is_goal: G,
+    ) -> SearchResult<Cell>
+    where
+        C: FnMut(&T, &T) -> Option<u32>,
+        H: FnMut(Cell) -> u32,
+        G: FnMut(Cell) -> bool,
+    {
+        let neighbors = |(x, y): Cell| {
+            let from = self.get(x, y);
+            self.neighbors4((x, y))
+                .filter_map(|(nx, ny, to)| Some(((nx, ny), cost(from, to)?)))
+                .collect::<Vec<_>>()
+        };
+        let mut result = astar(starts, neighbors, heuristic, is_goal);
+        result.grid_size = Some((self.len_x(), self.len_y()));
+        result
+    }
+}
+
+//--------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // example of the hill climbing puzzle
+    const HEIGHTMAP: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    fn heightmap() -> (Grid<u8>, Cell, Cell) {
+        let (grid, markers) = Grid::parse_with_markers(HEIGHTMAP, &['S', 'E'], |c| match c {
+            'S' => Ok::<_, ()>(b'a'),
+            'E' => Ok(b'z'),
+            c => Ok(c as u8),
+        })
+        .unwrap();
+        (grid, markers[0].1.tuple(), markers[1].1.tuple())
+    }
+
+    #[test]
+    fn teste_bfs() {
+        let (grid, start, end) = heightmap();
+        let climb = |from: &u8, to: &u8| *to <= from + 1;
+
+        let result = grid.bfs([start], climb, |c| c == end);
+        assert_eq!(result.goal, Some(end));
+        assert_eq!(result.goal_distance(), Some(31));
+        let path = result.path().unwrap();
+        assert_eq!((path.len(), path[0], path[31]), (32, start, end));
+
+        // from any of the lowest cells
+        let lowest: Vec<Cell> = grid
+            .iter_indexed()
+            .filter(|&(_, _, &h)| h == b'a')
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        let result = grid.bfs(lowest, climb, |c| c == end);
+        assert_eq!(result.goal_distance(), Some(29));
+
+        // full distance map
+        let distances = grid.bfs([start], climb, |_| false).to_grid();
+        assert_eq!(*distances.get(end.0, end.1), Some(31));
+        assert_eq!(*distances.get(0, 0), Some(0));
+    }
+
+    #[test]
+    fn teste_weighted() {
+        let grid = Grid::parse("1163\n1381\n2136\n9#11", |c| Ok::<_, ()>(c.to_digit(10))).unwrap();
+        let cost = |_: &Option<u32>, to: &Option<u32>| *to;
+        let end = (3, 3);
+
+        let dijkstra = grid.dijkstra([(0, 0)], cost, |c| c == end);
+        assert_eq!(dijkstra.goal_distance(), Some(9));
+
+        let manhattan = |(x, y): Cell| (3 - x + 3 - y) as u32;
+        let astar = grid.astar([(0, 0)], cost, manhattan, |c| c == end);
+        assert_eq!(astar.goal_distance(), Some(9));
+        assert_eq!(astar.path(), dijkstra.path());
+
+        let unreachable = grid.dijkstra([(0, 0)], cost, |c| c == (1, 3));
+        assert_eq!(unreachable.goal, None);
+        assert_eq!(unreachable.path_to((1, 3)), None);
+        assert_eq!(unreachable.to_grid().iter().filter(|d| d.is_some()).count(), 15);
+
+        // stopping at the goal leaves the cells still in the queue out
+        let early = grid.dijkstra([(0, 0)], cost, |c| c == (1, 0));
+        assert_eq!(early.goal_distance(), Some(1));
+        assert_eq!(early.distance((2, 0)), None);
+        assert!(early.distances().all(|(_, d)| d <= 1));
+    }
+
+    #[test]
+    fn teste_generic_nodes() {
+        // shortest number of steps to reach 10 from 1, doubling or adding 1
+        let result = bfs([1u32], |n| [n * 2, n + 1], |n| n == 10);
+        assert_eq!(result.path(), Some(vec![1, 2, 4, 5, 10]));
+    }
+
+    #[test]
+    fn teste_astar_inconsistent_heuristic() {
+        // the heuristic of B never overestimates, but drops by 5 on a step of cost 1, so C is reached first through A
+        let edges = |n: char| match n {
+            'S' => vec![('A', 1), ('B', 2)],
+            'A' => vec![('C', 4)],
+            'B' => vec![('C', 1)],
+            'C' => vec![('G', 4)],
+            _ => vec![],
+        };
+        let heuristic = |n: char| if n == 'B' { 5 } else { 0 };
+        let result = astar(['S'], edges, heuristic, |n| n == 'G');
+        assert_eq!(result.goal_distance(), Some(7));
+        assert_eq!(result.path(), Some(vec!['S', 'B', 'C', 'G']));
+        assert_eq!(result.distance('C'), Some(3));
+    }
+}
@@ -0,0 +1,236 @@
+//! An unbounded two-dimensional grid, storing only the cells that were set.
+//!
+//! Cells are keyed by signed coordinates, so the grid may grow towards any direction. The bounding box of the set
+//! cells is tracked, which allows iterating over them in row major order, converting to a dense `Grid` or rendering as
+//! text.
+
+use std::collections::HashMap;
+
+use super::{base2d::Base2d, grid::Grid};
+
+type Point = Base2d<i64>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    min: Point,
+    max: Point,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// creates an empty grid.
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            min: Point::new(0, 0),
+            max: Point::new(0, 0),
+        }
+    }
+
+    //------------------------------
+    // Single elements
+    //------------------------------
+
+    /// sets the value at `pos`, returning the previous one if the cell was already set.
+    pub fn insert(&mut self, pos: Point, value: T) -> Option<T> {
+        if self.cells.is_empty() {
+            self.min = pos;
+            self.max = pos;
+        } else {
+            self.min = Point::new(self.min.x.min(pos.x), self.min.y.min(pos.y));
+            self.max = Point::new(self.max.x.max(pos.x), self.max.y.max(pos.y));
+        }
+        self.cells.insert(pos, value)
+    }
+
+    /// unsets the cell at `pos`, returning its value. The bounding box shrinks if needed.
+    pub fn remove(&mut self, pos: Point) -> Option<T> {
+        let value = self.cells.remove(&pos)?;
+        let on_edge = pos.x == self.min.x || pos.x == self.max.x || pos.y == self.min.y || pos.y == self.max.y;
+        if on_edge {
+            self.update_bounds();
+        }
+        Some(value)
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    //------------------------------
+    // Multiple elements
+    //------------------------------
+
+    /// returns an iterator over the set cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&pos, v)| (pos, v))
+    }
+
+    /// returns an iterator over every position inside the bounding box in row major order, along with the value of
+    /// the cell if it is set.
+    pub fn iter_bounds(&self) -> impl Iterator<Item = (Point, Option<&T>)> {
+        let (min, max) = self.bounds().unwrap_or((Point::new(0, 0), Point::new(-1, -1)));
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+            .map(move |pos| (pos, self.cells.get(&pos)))
+    }
+
+    //------------------------------
+    // Helpers
+    //------------------------------
+
+    /// number of set cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// returns the top left (minimum) and bottom right (maximum) corners of the bounding box of the set cells, both
+    /// inclusive. Returns `None` if the grid is empty.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        if self.cells.is_empty() {
+            None
+        } else {
+            Some((self.min, self.max))
+        }
+    }
+
+    /// renders the bounding box as text, one line per row, with each cell mapped to a char by `f`. Unset cells are
+    /// handed as `None`.
+    pub fn to_text<F>(&self, f: F) -> String
+    where
+        F: Fn(Option<&T>) -> char,
+    {
+        let mut s = String::new();
+        for (pos, v) in self.iter_bounds() {
+            if pos.x == self.min.x && pos.y != self.min.y {
+                s.push('\n');
+            }
+            s.push(f(v));
+        }
+        s
+    }
+
+    //------------------------------
+    // Private
+    //------------------------------
+
+    /// recomputes the bounding box, an empty grid goes back to the bounds of `new`, so it compares equal to it.
+    fn update_bounds(&mut self) {
+        let mut positions = self.cells.keys();
+        let (min, max) = match positions.next() {
+            Some(&first) => positions.fold((first, first), |(min, max), p| {
+                (
+                    Point::new(min.x.min(p.x), min.y.min(p.y)),
+                    Point::new(max.x.max(p.x), max.y.max(p.y)),
+                )
+            }),
+            None => (Point::new(0, 0), Point::new(0, 0)),
+        };
+        self.min = min;
+        self.max = max;
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// converts the bounding box into a dense grid, unset cells take the `fill` value. Also returns the offset of the
+    /// grid, the cell at `pos` goes to `pos - offset` in the dense grid, so the offset is the top left corner of the
+    /// bounding box. An empty sparse grid yields an empty dense one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a side of the bounding box is longer than `usize::MAX`, the dense grid could never be allocated.
+    pub fn to_grid(&self, fill: T) -> (Grid<T>, Point) {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return (Grid::new(0, 0, fill), Point::new(0, 0)),
+        };
+        // the sides may not fit an i64 when the cells are far apart
+        let side = |min: i64, max: i64| {
+            usize::try_from(max as i128 - min as i128 + 1).expect("the bounding box is too large for a dense grid")
+        };
+        let len_x = side(min.x, max.x);
+        let len_y = side(min.y, max.y);
+
+        let mut grid = Grid::new(len_x, len_y, fill);
+        for (pos, v) in self.iter() {
+            let local = pos - min;
+            *grid.get_mut(local.x as usize, local.y as usize) = v.clone();
+        }
+        (grid, min)
+    }
+}
+
+//--------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn teste_insert_and_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Point::new(2, -1), 'a');
+        grid.insert(Point::new(-3, 4), 'b');
+        assert_eq!(grid.insert(Point::new(2, -1), 'c'), Some('a'));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.bounds(), Some((Point::new(-3, -1), Point::new(2, 4))));
+        assert_eq!(grid.get(Point::new(2, -1)), Some(&'c'));
+
+        assert_eq!(grid.remove(Point::new(-3, 4)), Some('b'));
+        assert_eq!(grid.bounds(), Some((Point::new(2, -1), Point::new(2, -1))));
+        assert_eq!(grid.remove(Point::new(-3, 4)), None);
+        grid.remove(Point::new(2, -1));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid, SparseGrid::new());
+    }
+
+    #[test]
+    fn teste_row_major_and_dense() {
+        let mut grid = SparseGrid::new();
+        grid.insert(Point::new(-1, 0), 1);
+        grid.insert(Point::new(1, -1), 2);
+        grid.insert(Point::new(0, 0), 3);
+
+        let values: Vec<Option<&i32>> = grid.iter_bounds().map(|(_, v)| v).collect();
+        assert_eq!(values, vec![None, None, Some(&2), Some(&1), Some(&3), None]);
+
+        let (dense, offset) = grid.to_grid(0);
+        assert_eq!(offset, Point::new(-1, -1));
+        assert_eq!(dense, Grid::from_vec(3, 2, vec![0, 0, 2, 1, 3, 0]));
+
+        assert_eq!(grid.to_text(|v| v.map_or('.', |_| '#')), "..#\n##.");
+        assert_eq!(SparseGrid::<i32>::new().to_text(|_| '#'), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "the bounding box is too large for a dense grid")]
+    fn teste_to_grid_too_large() {
+        let mut grid = SparseGrid::new();
+        grid.insert(Point::new(i64::MIN, 0), 1);
+        grid.insert(Point::new(i64::MAX, 0), 2);
+        grid.to_grid(0);
+    }
+}
@@ -8,8 +8,19 @@ pub mod day07;
 pub mod day08;
 pub mod day09;
 
+pub mod generator;
+
 pub mod helpers {
+    pub mod automaton;
     pub mod base2d;
+    pub mod base3d;
+    pub mod bit_grid;
+    pub mod fixed_grid;
     pub mod grid;
+    pub mod grid3;
+    pub mod packet;
     pub mod read;
+    pub mod rng;
+    pub mod search;
+    pub mod sparse_grid;
 }
@@ -1,9 +1,15 @@
 //! Just run the binary passing the day as argument. For exemple `cargo run 1`, runs the solution for day 01 puzzle.
 //!
 //! The puzzles inputs need to be added to the `inputs` folder.
+//!
+//! Random inputs for stress testing can be printed with `cargo run gen <day> --size N --seed S`. Both flags are
+//! optional, by default the size is 100 and the seed comes from the clock.
 
 use aoc2022::*;
-use std::env;
+use std::{
+    env,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 fn main() {
     let input = env::args().nth(1);
@@ -12,7 +18,15 @@ fn main() {
         return;
     }
 
-    match input.unwrap().parse().unwrap() {
+    let input = input.unwrap();
+    if input == "gen" {
+        if let Err(e) = gen(env::args().skip(2)) {
+            eprintln!("{}", e);
+        }
+        return;
+    }
+
+    match input.parse().unwrap() {
         1 => day01::run(),
         2 => day02::run(),
         3 => day03::run(),
@@ -25,3 +39,33 @@ fn main() {
         _ => println!("Invalid input argument."),
     }
 }
+
+/// parses the arguments of the `gen` command and prints the generated input. The seed is printed to stderr, so it
+/// does not get mixed with the input when redirecting.
+fn gen(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let day: u8 = args
+        .next()
+        .ok_or("Usage: gen <day> [--size N] [--seed S]")?
+        .parse()
+        .or(Err("Could not parse the day."))?;
+
+    let mut size = 100;
+    let mut seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or(format!("Missing value for {}.", flag))?;
+        match flag.as_str() {
+            "--size" => size = value.parse().or(Err("Could not parse the size."))?,
+            "--seed" => seed = value.parse().or(Err("Could not parse the seed."))?,
+            _ => return Err(format!("Unknown flag {}.", flag)),
+        }
+    }
+
+    let input = generator::generate(day, size, seed).ok_or(format!("No generator for day {}.", day))?;
+    eprintln!("seed {}", seed);
+    println!("{}", input);
+    Ok(())
+}